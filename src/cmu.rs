@@ -1,26 +1,31 @@
 //! CMU (Clock Management Unit)
 //!
-//! This first implementation tries to anticipate what can later be done with the clocks, but
-//! only implements what is essential do have working delays.
+//! This implementation tries to anticipate what can later be done with the clocks, but so far
+//! only implements the configuration of the high frequency clock tree (HFCLK source, HFRCO band
+//! and the HFCORECLK/HFPERCLK divisors), which is what is needed for delays and timers to know
+//! their actual frequencies, and the selection of sources for the low frequency branches that
+//! drive the low energy peripherals.
 //!
 //! The workflow is probably going to be:
 //! * Take the SVD-derived peripheral (CMU implementing CMUExt), consume it into a Cmu that we can
-//!   actually work on.
+//!   actually work on, and configure it using its `with_...` builder methods.
 //! * Split (or freeze?) the Cmu into all the configured Clocks that are now objects which know
 //!   their timing and cant' be changed.
-//!   * This splitting process may become more-phased later, such that one could freeze LFXO based
-//!     clocks when they are up, and still build HFXO based clocks on demand.
+//!   * This can also be done in phases, such that one can freeze LFXO based clocks when they are
+//!     up, and still build HFXO based clocks on demand.
 //! * Timers, delays, but also timed peripherals (eg. UARTs) can then be built from those clocks;
 //!   not sure whether they'll need to consume them, it may suffice to get a long-lived reference
 //!   to them.
 //!     * If clocks need to be counsumed, the peripherals could still "spit them out" again, and if
-//!       one owns all the parts of a frozen clock, one might change that one again. (Esp. if we
-//!       want to model deep sleep states correcly; starting a sleep state that disables HF clocks
-//!       should require having a writable reference to that clock.)
+//!       one owns all the parts of a frozen clock, one can thaw and change that one again. (Esp.
+//!       if we want to model deep sleep states correcly; starting a sleep state that disables HF
+//!       clocks should require having a writable reference to that clock.)
+
 
 use registers;
 
 use crate::time_util::Hertz;
+use crate::bitband;
 
 pub trait CMUExt {
     fn constrain(self) -> Cmu;
@@ -28,76 +33,621 @@ pub trait CMUExt {
 
 impl CMUExt for registers::CMU {
     fn constrain(self) -> Cmu {
-        Cmu { _private: () }
+        Cmu {
+            register: self,
+            hfclk: HFClockSource::HFRCO(HFRCOBand::default()),
+            hfrco_calibration: None,
+            hfcoreclk_divisor: 1,
+            hfperclk_divisor: 1,
+            lfa: LFClockSource::Disabled,
+            lfb: LFClockSource::Disabled,
+            #[cfg(feature = "chip-efr32xg1")]
+            lfe: LFClockSource::Disabled,
+            gpio: Some(GPIOClk { _private: () }),
+            clock_outputs: Some(ClockOutputs {
+                clkout0: ClkOut0 { _private: () },
+                clkout1: ClkOut1 { _private: () },
+            }),
+            hf_frozen: false,
+            lfa_frozen: false,
+            lfb_frozen: false,
+            #[cfg(feature = "chip-efr32xg1")]
+            lfe_frozen: false,
+        }
     }
 }
 
+/// The CMU in configuration state.
+///
+/// The clock configuration is built up using the `with_...` methods (which only record what is
+/// requested), and only gets applied to the hardware when the Cmu is split into its `Clocks`, or
+/// when the individual clock branches are frozen.
+///
+/// Freezing a branch hands out the clocks derived from it, and prevents its configuration from
+/// being changed until all those clocks are given back by thawing it. This way, the LF clocks can
+/// be frozen early (eg. to run an RTC from LFXO), while the HF clock tree is only configured and
+/// frozen when it is needed:
+///
+/// ```ignore
+/// let mut cmu = p.CMU.constrain().with_lfa(LFClockSource::LFXO);
+/// let lfa = cmu.freeze_lfa()?;
+/// // ... much later
+/// let mut cmu = cmu.with_hfxo(Hertz(48_000_000));
+/// let hf = cmu.freeze_hf()?;
+/// ```
 pub struct Cmu {
-    // Just make sure this can't be created from outside; becomes obsolete when there are other
-    // non-pub fields.
-    _private: (),
+    register: registers::CMU,
+    hfclk: HFClockSource,
+    /// Tuning value and resulting frequency of the HFRCO as found by calibrate_hfrco
+    hfrco_calibration: Option<(u8, Hertz)>,
+    hfcoreclk_divisor: u16,
+    hfperclk_divisor: u16,
+    lfa: LFClockSource,
+    lfb: LFClockSource,
+    #[cfg(feature = "chip-efr32xg1")]
+    lfe: LFClockSource,
+    gpio: Option<GPIOClk>,
+    clock_outputs: Option<ClockOutputs>,
+    hf_frozen: bool,
+    lfa_frozen: bool,
+    lfb_frozen: bool,
+    #[cfg(feature = "chip-efr32xg1")]
+    lfe_frozen: bool,
 }
 
 pub trait FrozenClock {
     fn get_frequency(&self) -> Hertz;
 }
 
+/// Error conditions that can occur when the configuration is applied.
+#[derive(Debug)]
+pub enum Error {
+    /// The HFXO did not become ready in time; typically, this means that no crystal is fitted.
+    HFXOTimeout,
+    /// The LFXO did not become ready in time; typically, this means that no crystal is fitted.
+    LFXOTimeout,
+    /// The LFRCO did not become ready in time.
+    LFRCOTimeout,
+    /// The clocks were already handed out, and need to be given back before they can be frozen
+    /// again.
+    AlreadyFrozen,
+    /// HFRCO calibration was requested, but HFCLK is configured to run from another source.
+    NotRunningFromHFRCO,
+}
+
+/// Number of status register polls after which an oscillator that has not become ready is
+/// considered not to start at all.
+///
+/// This is a rough number that gives a crystal around a second even at the highest clock rates.
+const OSCILLATOR_TIMEOUT: u32 = 10_000_000;
+
+/// Sources the HFCLK (and thus the core and high frequency peripheral clocks) can be derived from.
+#[derive(Clone, Copy)]
+pub enum HFClockSource {
+    /// The internal RC oscillator, running in the given band.
+    HFRCO(HFRCOBand),
+    /// An external crystal (or clock) on the HFXO pins, running at the given frequency.
+    HFXO(Hertz),
+}
+
+/// Oscillators that can be trimmed using the calibration counter
+#[derive(Clone, Copy)]
+enum TunableOscillator {
+    HFRCO,
+    AUXHFRCO,
+}
+
+#[cfg(feature = "chip-efm32gg")]
+const TUNING_MAX: u8 = 0xff;
+#[cfg(feature = "chip-efr32xg1")]
+const TUNING_MAX: u8 = 0x7f;
+
+// Whether a higher TUNING value makes the RC oscillators run faster; on Series 1 devices, it makes
+// them run slower.
+#[cfg(feature = "chip-efm32gg")]
+const TUNING_RAISES_FREQUENCY: bool = true;
+#[cfg(feature = "chip-efr32xg1")]
+const TUNING_RAISES_FREQUENCY: bool = false;
+
+/// Reference clocks an RC oscillator can be calibrated against
+#[derive(Clone, Copy)]
+pub enum CalibrationReference {
+    /// A 32.768kHz crystal at the LFXO pins
+    LFXO,
+    /// A crystal at the HFXO pins running at the given frequency
+    HFXO(Hertz),
+}
+
+impl CalibrationReference {
+    fn frequency(self) -> Hertz {
+        match self {
+            CalibrationReference::LFXO => LFClockSource::LFXO.frequency(),
+            CalibrationReference::HFXO(frequency) => frequency,
+        }
+    }
+}
+
+/// Sources the low frequency clock branches (LFACLK, LFBCLK and, on EFR32, LFECLK) can be driven
+/// from.
+#[derive(Clone, Copy, PartialEq)]
+pub enum LFClockSource {
+    /// The branch is not clocked; peripherals on it will not run.
+    Disabled,
+    /// The internal 32.768kHz RC oscillator
+    LFRCO,
+    /// A 32.768kHz crystal (or external clock) at the LFXO pins
+    LFXO,
+    /// The internal 1kHz ultra low frequency RC oscillator, which keeps running down to EM3
+    ULFRCO,
+}
+
+impl LFClockSource {
+    fn frequency(self) -> Hertz {
+        Hertz(match self {
+            LFClockSource::Disabled => 0,
+            LFClockSource::LFRCO => 32_768,
+            LFClockSource::LFXO => 32_768,
+            LFClockSource::ULFRCO => 1_000,
+        })
+    }
+}
+
+/// Frequency bands of the HFRCO.
+///
+/// The frequencies are the nominal band frequencies; the exact frequency the oscillator will run
+/// at is only known to within the precision of the factory calibration.
+#[cfg(feature = "chip-efm32gg")]
+#[derive(Clone, Copy)]
+pub enum HFRCOBand {
+    MHz1,
+    MHz7,
+    MHz11,
+    MHz14,
+    MHz21,
+    MHz28,
+}
+
+#[cfg(feature = "chip-efm32gg")]
+impl HFRCOBand {
+    fn frequency(self) -> Hertz {
+        Hertz(match self {
+            HFRCOBand::MHz1 => 1_000_000,
+            HFRCOBand::MHz7 => 7_000_000,
+            HFRCOBand::MHz11 => 11_000_000,
+            HFRCOBand::MHz14 => 14_000_000,
+            HFRCOBand::MHz21 => 21_000_000,
+            HFRCOBand::MHz28 => 28_000_000,
+        })
+    }
+
+    fn variant(self) -> registers::cmu::hfrcoctrl::BANDW {
+        use registers::cmu::hfrcoctrl::BANDW;
+        match self {
+            HFRCOBand::MHz1 => BANDW::_1MHZ,
+            HFRCOBand::MHz7 => BANDW::_7MHZ,
+            HFRCOBand::MHz11 => BANDW::_11MHZ,
+            HFRCOBand::MHz14 => BANDW::_14MHZ,
+            HFRCOBand::MHz21 => BANDW::_21MHZ,
+            HFRCOBand::MHz28 => BANDW::_28MHZ,
+        }
+    }
+}
+
+#[cfg(feature = "chip-efm32gg")]
+impl Default for HFRCOBand {
+    /// The band the HFRCO is in after reset
+    fn default() -> Self {
+        HFRCOBand::MHz14
+    }
+}
+
+/// Frequency bands of the HFRCO.
+///
+/// The frequencies are the nominal band frequencies; the exact frequency the oscillator will run
+/// at is only known to within the precision of the factory calibration.
+#[cfg(feature = "chip-efr32xg1")]
+#[derive(Clone, Copy)]
+pub enum HFRCOBand {
+    MHz4,
+    MHz7,
+    MHz13,
+    MHz16,
+    MHz19,
+    MHz26,
+    MHz32,
+    MHz38,
+}
+
+#[cfg(feature = "chip-efr32xg1")]
+impl HFRCOBand {
+    fn frequency(self) -> Hertz {
+        Hertz(match self {
+            HFRCOBand::MHz4 => 4_000_000,
+            HFRCOBand::MHz7 => 7_000_000,
+            HFRCOBand::MHz13 => 13_000_000,
+            HFRCOBand::MHz16 => 16_000_000,
+            HFRCOBand::MHz19 => 19_000_000,
+            HFRCOBand::MHz26 => 26_000_000,
+            HFRCOBand::MHz32 => 32_000_000,
+            HFRCOBand::MHz38 => 38_000_000,
+        })
+    }
+
+    /// Number of the DEVINFO HFRCOCALn register that holds the band's HFRCOCTRL value
+    fn calibration_index(self) -> u8 {
+        match self {
+            HFRCOBand::MHz4 => 0,
+            HFRCOBand::MHz7 => 3,
+            HFRCOBand::MHz13 => 6,
+            HFRCOBand::MHz16 => 7,
+            HFRCOBand::MHz19 => 8,
+            HFRCOBand::MHz26 => 10,
+            HFRCOBand::MHz32 => 11,
+            HFRCOBand::MHz38 => 12,
+        }
+    }
+}
+
+#[cfg(feature = "chip-efr32xg1")]
+impl Default for HFRCOBand {
+    /// The band the HFRCO is in after reset
+    fn default() -> Self {
+        HFRCOBand::MHz19
+    }
+}
+
 pub struct Clocks {
     pub hfcoreclk: HFCoreClk,
     pub i2c0: I2C0Clk,
     pub gpio: GPIOClk,
+    pub clock_outputs: ClockOutputs,
     pub timer0: TIMER0Clk,
     pub timer1: TIMER1Clk,
     #[cfg(feature = "_has_timer2")]
     pub timer2: TIMER2Clk,
     #[cfg(feature = "_has_timer3")]
     pub timer3: TIMER3Clk,
+    #[cfg(feature = "_has_i2c1")]
+    pub i2c1: I2C1Clk,
+    pub usart0: USART0Clk,
+    pub usart1: USART1Clk,
+    #[cfg(feature = "_has_usart2")]
+    pub usart2: USART2Clk,
+    #[cfg(feature = "_has_uart0")]
+    pub uart0: UART0Clk,
+    #[cfg(feature = "_has_uart1")]
+    pub uart1: UART1Clk,
+    #[cfg(feature = "_has_cryotimer")]
+    pub cryotimer: CRYOTIMERClk,
+    pub acmp0: ACMP0Clk,
+    pub acmp1: ACMP1Clk,
+    #[cfg(feature = "_has_vcmp")]
+    pub vcmp: VCMPClk,
+    pub adc0: ADC0Clk,
+    #[cfg(feature = "_has_dac0")]
+    pub dac0: DAC0Clk,
+    #[cfg(feature = "_has_idac0")]
+    pub idac0: IDAC0Clk,
+    pub prs: PRSClk,
+    #[cfg(feature = "_has_dma")]
+    pub dma: DMAClk,
+    #[cfg(feature = "_has_ldma")]
+    pub ldma: LDMAClk,
+    #[cfg(feature = "_has_aes")]
+    pub aes: AESClk,
+    #[cfg(feature = "_has_crypto")]
+    pub crypto: CRYPTOClk,
+    #[cfg(feature = "_has_gpcrc")]
+    pub gpcrc: GPCRCClk,
+    #[cfg(feature = "_has_usb")]
+    pub usb: USBClk,
+    #[cfg(feature = "_has_ebi")]
+    pub ebi: EBIClk,
+
+    pub lfaclk: LFAClk,
+    pub lfbclk: LFBClk,
+    #[cfg(feature = "chip-efr32xg1")]
+    pub lfeclk: LFEClk,
+    #[cfg(feature = "_has_rtc")]
+    pub rtc: RTCClk,
+    #[cfg(feature = "_has_rtcc")]
+    pub rtcc: RTCCClk,
+    pub letimer0: LETIMER0Clk,
+    #[cfg(feature = "_has_lcd")]
+    pub lcd: LCDClk,
+    #[cfg(feature = "_has_lesense")]
+    pub lesense: LESENSEClk,
+    pub leuart0: LEUART0Clk,
+    #[cfg(feature = "_has_leuart1")]
+    pub leuart1: LEUART1Clk,
+    pub pcnt0: PCNT0Clk,
+    #[cfg(feature = "_has_pcnt1")]
+    pub pcnt1: PCNT1Clk,
+    #[cfg(feature = "_has_pcnt2")]
+    pub pcnt2: PCNT2Clk,
 }
 
-pub struct I2C0Clk {
-    _private: (),
+/// The clocks derived from the high frequency clock tree, obtained by `Cmu::freeze_hf()`.
+pub struct HFClocks {
+    pub hfcoreclk: HFCoreClk,
+    pub i2c0: I2C0Clk,
+    #[cfg(feature = "_has_i2c1")]
+    pub i2c1: I2C1Clk,
+    pub usart0: USART0Clk,
+    pub usart1: USART1Clk,
+    #[cfg(feature = "_has_usart2")]
+    pub usart2: USART2Clk,
+    #[cfg(feature = "_has_uart0")]
+    pub uart0: UART0Clk,
+    #[cfg(feature = "_has_uart1")]
+    pub uart1: UART1Clk,
+    pub timer0: TIMER0Clk,
+    pub timer1: TIMER1Clk,
+    #[cfg(feature = "_has_timer2")]
+    pub timer2: TIMER2Clk,
+    #[cfg(feature = "_has_timer3")]
+    pub timer3: TIMER3Clk,
+    #[cfg(feature = "_has_cryotimer")]
+    pub cryotimer: CRYOTIMERClk,
+    pub acmp0: ACMP0Clk,
+    pub acmp1: ACMP1Clk,
+    #[cfg(feature = "_has_vcmp")]
+    pub vcmp: VCMPClk,
+    pub adc0: ADC0Clk,
+    #[cfg(feature = "_has_dac0")]
+    pub dac0: DAC0Clk,
+    #[cfg(feature = "_has_idac0")]
+    pub idac0: IDAC0Clk,
+    pub prs: PRSClk,
+    #[cfg(feature = "_has_dma")]
+    pub dma: DMAClk,
+    #[cfg(feature = "_has_ldma")]
+    pub ldma: LDMAClk,
+    #[cfg(feature = "_has_aes")]
+    pub aes: AESClk,
+    #[cfg(feature = "_has_crypto")]
+    pub crypto: CRYPTOClk,
+    #[cfg(feature = "_has_gpcrc")]
+    pub gpcrc: GPCRCClk,
+    #[cfg(feature = "_has_usb")]
+    pub usb: USBClk,
+    #[cfg(feature = "_has_ebi")]
+    pub ebi: EBIClk,
 }
 
-impl I2C0Clk {
-    pub fn enable(&mut self) {
-        // UNSAFE FIXME this actually is still unsafe because we don't really have an exclusive
-        // pointer there and would need to set the bit using bit-banding, but the current svd2rust
-        // registers can't use that.
-        //
-        // A better way is under discussion: https://github.com/japaric/svd2rust/issues/226
-        unsafe {
-            let cmu = &*registers::CMU::ptr();
-            cmu.hfperclken0.modify(|_, w| w.i2c0().set_bit());
-        }
-    }
+/// The clocks derived from LFACLK, obtained by `Cmu::freeze_lfa()`.
+pub struct LFAClocks {
+    pub lfaclk: LFAClk,
+    #[cfg(feature = "_has_rtc")]
+    pub rtc: RTCClk,
+    pub letimer0: LETIMER0Clk,
+    #[cfg(feature = "_has_lcd")]
+    pub lcd: LCDClk,
+    #[cfg(feature = "_has_lesense")]
+    pub lesense: LESENSEClk,
+    pub pcnt0: PCNT0Clk,
+    #[cfg(feature = "_has_pcnt1")]
+    pub pcnt1: PCNT1Clk,
+    #[cfg(feature = "_has_pcnt2")]
+    pub pcnt2: PCNT2Clk,
+}
+
+/// The clocks derived from LFBCLK, obtained by `Cmu::freeze_lfb()`.
+pub struct LFBClocks {
+    pub lfbclk: LFBClk,
+    pub leuart0: LEUART0Clk,
+    #[cfg(feature = "_has_leuart1")]
+    pub leuart1: LEUART1Clk,
+}
+
+/// The clocks derived from LFECLK, obtained by `Cmu::freeze_lfe()`.
+#[cfg(feature = "chip-efr32xg1")]
+pub struct LFEClocks {
+    pub lfeclk: LFEClk,
+    #[cfg(feature = "_has_rtcc")]
+    pub rtcc: RTCCClk,
 }
 
+/// Clock gates for peripherals that are switched on by one or more bits in one of the CMU's
+/// (non-synchronized) clock enable registers, given by their bit positions.
+///
+/// They report the frequency of the clock they are derived from, unless they are marked as
+/// `bus_only` because the peripheral counts a different clock (which is not handled here).
+macro_rules! clkgate {
+    ($Clk: ident, $clkenX: ident, $($bit: expr),+) => {
+
+        pub struct $Clk {
+            frequency: Hertz,
+        }
+
+        clkgate!(@switch $Clk, $clkenX, $($bit),+);
 
-macro_rules! timerclk {
-    ($TIMERnClk: ident, $timerN: ident) => {
+        impl FrozenClock for $Clk {
+            fn get_frequency(&self) -> Hertz {
+                self.frequency
+            }
+        }
+    };
+    (bus_only $Clk: ident, $clkenX: ident, $($bit: expr),+) => {
 
-        pub struct $TIMERnClk {
+        pub struct $Clk {
             _private: (),
         }
 
-        impl $TIMERnClk {
+        clkgate!(@switch $Clk, $clkenX, $($bit),+);
+    };
+    (@switch $Clk: ident, $clkenX: ident, $($bit: expr),+) => {
+
+        impl $Clk {
+            pub fn enable(&mut self) {
+                self.set(true);
+            }
+
+            pub fn disable(&mut self) {
+                self.set(false);
+            }
+
+            fn set(&mut self, value: bool) {
+                // Unsafe: Bit-banding writes only the bits owned by this clock gate atomically, so
+                // this can not race with other gates in the same register being switched.
+                unsafe {
+                    let cmu = &*registers::CMU::ptr();
+                    $( bitband::change_bit(&cmu.$clkenX, $bit, value); )+
+                }
+            }
+        }
+    };
+}
+
+/// Clock gates for peripherals in one of the low frequency domains, given by their bit position in
+/// the LFxCLKEN0 register.
+///
+/// They report the frequency of their low frequency branch; any prescaler in the LFxPRESC0
+/// registers is left at its default of 1.
+macro_rules! leclk {
+    ($LEClk: ident, $lfXclken0: ident, $bit: expr) => {
+
+        pub struct $LEClk {
+            frequency: Hertz,
+        }
+
+        impl $LEClk {
             pub fn enable(&mut self) {
-                // UNSAFE FIXME as with I2CClk
+                self.set(true);
+            }
+
+            pub fn disable(&mut self) {
+                self.set(false);
+            }
+
+            fn set(&mut self, value: bool) {
+                // Unsafe: as in clkgate!
                 unsafe {
                     let cmu = &*registers::CMU::ptr();
-                    cmu.hfperclken0.modify(|_, w| w.$timerN().set_bit());
+                    // Writes to the LF clock enable registers need to be synchronized into the low
+                    // frequency domain before the register can be written again.
+                    while cmu.syncbusy.read().$lfXclken0().bit() {}
+                    bitband::change_bit(&cmu.$lfXclken0, $bit, value);
                 }
             }
         }
+
+        impl FrozenClock for $LEClk {
+            fn get_frequency(&self) -> Hertz {
+                self.frequency
+            }
+        }
     }
 }
 
-timerclk!(TIMER0Clk, timer0);
-timerclk!(TIMER1Clk, timer1);
+#[cfg(feature = "chip-efm32gg")]
+clkgate!(I2C0Clk, hfperclken0, 11);
+#[cfg(feature = "chip-efr32xg1")]
+clkgate!(I2C0Clk, hfperclken0, 7);
+#[cfg(feature = "_has_i2c1")]
+clkgate!(I2C1Clk, hfperclken0, 12);
+#[cfg(feature = "chip-efm32gg")]
+clkgate!(USART0Clk, hfperclken0, 0);
+#[cfg(feature = "chip-efr32xg1")]
+clkgate!(USART0Clk, hfperclken0, 2);
+#[cfg(feature = "chip-efm32gg")]
+clkgate!(USART1Clk, hfperclken0, 1);
+#[cfg(feature = "chip-efr32xg1")]
+clkgate!(USART1Clk, hfperclken0, 3);
+#[cfg(feature = "_has_usart2")]
+clkgate!(USART2Clk, hfperclken0, 2);
+#[cfg(feature = "_has_uart0")]
+clkgate!(UART0Clk, hfperclken0, 3);
+#[cfg(feature = "_has_uart1")]
+clkgate!(UART1Clk, hfperclken0, 4);
+#[cfg(feature = "chip-efm32gg")]
+clkgate!(TIMER0Clk, hfperclken0, 5);
+#[cfg(feature = "chip-efr32xg1")]
+clkgate!(TIMER0Clk, hfperclken0, 0);
+#[cfg(feature = "chip-efm32gg")]
+clkgate!(TIMER1Clk, hfperclken0, 6);
+#[cfg(feature = "chip-efr32xg1")]
+clkgate!(TIMER1Clk, hfperclken0, 1);
 #[cfg(feature = "_has_timer2")]
-timerclk!(TIMER2Clk, timer2);
+clkgate!(TIMER2Clk, hfperclken0, 7);
 #[cfg(feature = "_has_timer3")]
-timerclk!(TIMER3Clk, timer3);
+clkgate!(TIMER3Clk, hfperclken0, 8);
+// Only the bus interface; the counter runs from an LF oscillator selected in the CRYOTIMER itself
+#[cfg(feature = "_has_cryotimer")]
+clkgate!(bus_only CRYOTIMERClk, hfperclken0, 6);
+#[cfg(feature = "chip-efm32gg")]
+clkgate!(ACMP0Clk, hfperclken0, 9);
+#[cfg(feature = "chip-efr32xg1")]
+clkgate!(ACMP0Clk, hfperclken0, 4);
+#[cfg(feature = "chip-efm32gg")]
+clkgate!(ACMP1Clk, hfperclken0, 10);
+#[cfg(feature = "chip-efr32xg1")]
+clkgate!(ACMP1Clk, hfperclken0, 5);
+#[cfg(feature = "_has_vcmp")]
+clkgate!(VCMPClk, hfperclken0, 14);
+#[cfg(feature = "chip-efm32gg")]
+clkgate!(ADC0Clk, hfperclken0, 16);
+#[cfg(feature = "chip-efr32xg1")]
+clkgate!(ADC0Clk, hfperclken0, 8);
+#[cfg(feature = "_has_dac0")]
+clkgate!(DAC0Clk, hfperclken0, 17);
+#[cfg(feature = "_has_idac0")]
+clkgate!(IDAC0Clk, hfperclken0, 9);
+#[cfg(feature = "chip-efm32gg")]
+clkgate!(PRSClk, hfperclken0, 15);
+#[cfg(feature = "chip-efr32xg1")]
+clkgate!(PRSClk, hfbusclken0, 3);
+#[cfg(feature = "_has_dma")]
+clkgate!(DMAClk, hfcoreclken0, 0);
+#[cfg(feature = "_has_ldma")]
+clkgate!(LDMAClk, hfbusclken0, 4);
+#[cfg(feature = "_has_aes")]
+clkgate!(AESClk, hfcoreclken0, 1);
+#[cfg(feature = "_has_crypto")]
+clkgate!(CRYPTOClk, hfbusclken0, 1);
+#[cfg(feature = "_has_gpcrc")]
+clkgate!(GPCRCClk, hfbusclken0, 5);
+// This enables both the USB interface (USB) and the USB core clock (USBC); the latter's source
+// selection is left at its default.
+#[cfg(feature = "_has_usb")]
+clkgate!(USBClk, hfcoreclken0, 3, 2);
+#[cfg(feature = "_has_ebi")]
+clkgate!(EBIClk, hfcoreclken0, 5);
+
+#[cfg(feature = "_has_rtc")]
+leclk!(RTCClk, lfaclken0, 1);
+#[cfg(feature = "chip-efm32gg")]
+leclk!(LETIMER0Clk, lfaclken0, 2);
+#[cfg(feature = "chip-efr32xg1")]
+leclk!(LETIMER0Clk, lfaclken0, 0);
+#[cfg(feature = "_has_lcd")]
+leclk!(LCDClk, lfaclken0, 3);
+#[cfg(feature = "_has_lesense")]
+leclk!(LESENSEClk, lfaclken0, 0);
+leclk!(LEUART0Clk, lfbclken0, 0);
+#[cfg(feature = "_has_leuart1")]
+leclk!(LEUART1Clk, lfbclken0, 1);
+#[cfg(feature = "_has_rtcc")]
+leclk!(RTCCClk, lfeclken0, 0);
+
+// The pulse counters are clocked from LFACLK unless configured to count an external pin; their
+// clock enable register is not synchronized.
+clkgate!(PCNT0Clk, pcntctrl, 0);
+#[cfg(feature = "_has_pcnt1")]
+clkgate!(PCNT1Clk, pcntctrl, 2);
+#[cfg(feature = "_has_pcnt2")]
+clkgate!(PCNT2Clk, pcntctrl, 4);
+
+// Bit positions of the GPIO and LE interface clock enables, which are not handed out as clock gates
+// of their own.
+#[cfg(feature = "chip-efm32gg")]
+const GPIO_CLKEN_BIT: u8 = 13;
+#[cfg(feature = "chip-efm32gg")]
+const LE_CLKEN_BIT: u8 = 4;
+#[cfg(feature = "chip-efr32xg1")]
+const GPIO_CLKEN_BIT: u8 = 2;
+#[cfg(feature = "chip-efr32xg1")]
+const LE_CLKEN_BIT: u8 = 0;
 
 pub struct GPIOClk {
     _private: (),
@@ -105,48 +655,1035 @@ pub struct GPIOClk {
 
 impl GPIOClk {
     pub fn enable(&mut self) {
-        // UNSAFE FIXME as with I2CClk
+        self.set(true);
+    }
+
+    pub fn disable(&mut self) {
+        self.set(false);
+    }
+
+    fn set(&mut self, value: bool) {
+        // Unsafe: as in clkgate!
         unsafe {
             let cmu = &*registers::CMU::ptr();
             #[cfg(feature = "chip-efm32gg")]
-            cmu.hfperclken0.modify(|_, w| w.gpio().bit(true));
+            bitband::change_bit(&cmu.hfperclken0, GPIO_CLKEN_BIT, value);
             #[cfg(feature = "chip-efr32xg1")]
-            cmu.hfbusclken0.modify(|_, w| w.gpio().bit(true));
+            bitband::change_bit(&cmu.hfbusclken0, GPIO_CLKEN_BIT, value);
         }
     }
 }
 
 impl Cmu {
-    pub fn split(self) -> Clocks {
-        Clocks {
-            hfcoreclk: HFCoreClk { _private: () },
-            i2c0: I2C0Clk { _private: () },
-            gpio: GPIOClk { _private: () },
-            timer0: TIMER0Clk { _private: () },
-            timer1: TIMER1Clk { _private: () },
+    /// Run HFCLK from the HFRCO in the given band.
+    ///
+    /// This is the default, with the band the device comes out of reset in.
+    pub fn with_hfrco(mut self, band: HFRCOBand) -> Self {
+        assert!(!self.hf_frozen, "HF clocks are frozen");
+        self.hfclk = HFClockSource::HFRCO(band);
+        self.hfrco_calibration = None;
+        self
+    }
+
+    /// Run HFCLK from a crystal (or external clock) attached to the HFXO pins, which runs at the
+    /// given frequency.
+    pub fn with_hfxo(mut self, frequency: Hertz) -> Self {
+        assert!(!self.hf_frozen, "HF clocks are frozen");
+        self.hfclk = HFClockSource::HFXO(frequency);
+        self.hfrco_calibration = None;
+        self
+    }
+
+    /// Set the divisor by which HFCORECLK (and with it the CPU and the SysTick) is derived from
+    /// HFCLK.
+    ///
+    /// On EFM32GG, this needs to be a power of two up to 512; on EFR32xG1, it can be any number
+    /// from 1 to 512.
+    pub fn with_hfcoreclk_divisor(mut self, divisor: u16) -> Self {
+        assert!(!self.hf_frozen, "HF clocks are frozen");
+        assert!(divisor_is_valid(divisor), "Unsupported HFCORECLK divisor");
+        self.hfcoreclk_divisor = divisor;
+        self
+    }
+
+    /// Set the divisor by which HFPERCLK (which drives timers, I2C and other high frequency
+    /// peripherals) is derived from HFCLK.
+    ///
+    /// The same restrictions as for the HFCORECLK divisor apply.
+    pub fn with_hfperclk_divisor(mut self, divisor: u16) -> Self {
+        assert!(!self.hf_frozen, "HF clocks are frozen");
+        assert!(divisor_is_valid(divisor), "Unsupported HFPERCLK divisor");
+        self.hfperclk_divisor = divisor;
+        self
+    }
+
+    /// Drive the LFACLK branch (RTC, LETIMER0, LCD, LESENSE) from the given source.
+    ///
+    /// By default, the branch is disabled.
+    pub fn with_lfa(mut self, source: LFClockSource) -> Self {
+        assert!(!self.lfa_frozen, "LFA clocks are frozen");
+        self.lfa = source;
+        self
+    }
+
+    /// Drive the LFBCLK branch (LEUARTs) from the given source.
+    ///
+    /// By default, the branch is disabled.
+    pub fn with_lfb(mut self, source: LFClockSource) -> Self {
+        assert!(!self.lfb_frozen, "LFB clocks are frozen");
+        self.lfb = source;
+        self
+    }
+
+    /// Drive the LFECLK branch (RTCC) from the given source.
+    ///
+    /// By default, the branch is disabled.
+    #[cfg(feature = "chip-efr32xg1")]
+    pub fn with_lfe(mut self, source: LFClockSource) -> Self {
+        assert!(!self.lfe_frozen, "LFE clocks are frozen");
+        self.lfe = source;
+        self
+    }
+
+    /// Apply the configured clock tree, and split the CMU into the individual clocks.
+    ///
+    /// This freezes all the clock branches at once; see the `freeze_...` methods for how to do
+    /// that step by step.
+    pub fn split(mut self) -> Result<Clocks, Error> {
+        let gpio = self.take_gpio().ok_or(Error::AlreadyFrozen)?;
+        let clock_outputs = self.take_clock_outputs().ok_or(Error::AlreadyFrozen)?;
+        let hf = self.freeze_hf()?;
+        let lfa = self.freeze_lfa()?;
+        let lfb = self.freeze_lfb()?;
+        #[cfg(feature = "chip-efr32xg1")]
+        let lfe = self.freeze_lfe()?;
+
+        Ok(Clocks {
+            hfcoreclk: hf.hfcoreclk,
+            i2c0: hf.i2c0,
+            gpio,
+            clock_outputs,
+            timer0: hf.timer0,
+            timer1: hf.timer1,
+            #[cfg(feature = "_has_timer2")]
+            timer2: hf.timer2,
+            #[cfg(feature = "_has_timer3")]
+            timer3: hf.timer3,
+            #[cfg(feature = "_has_i2c1")]
+            i2c1: hf.i2c1,
+            usart0: hf.usart0,
+            usart1: hf.usart1,
+            #[cfg(feature = "_has_usart2")]
+            usart2: hf.usart2,
+            #[cfg(feature = "_has_uart0")]
+            uart0: hf.uart0,
+            #[cfg(feature = "_has_uart1")]
+            uart1: hf.uart1,
+            #[cfg(feature = "_has_cryotimer")]
+            cryotimer: hf.cryotimer,
+            acmp0: hf.acmp0,
+            acmp1: hf.acmp1,
+            #[cfg(feature = "_has_vcmp")]
+            vcmp: hf.vcmp,
+            adc0: hf.adc0,
+            #[cfg(feature = "_has_dac0")]
+            dac0: hf.dac0,
+            #[cfg(feature = "_has_idac0")]
+            idac0: hf.idac0,
+            prs: hf.prs,
+            #[cfg(feature = "_has_dma")]
+            dma: hf.dma,
+            #[cfg(feature = "_has_ldma")]
+            ldma: hf.ldma,
+            #[cfg(feature = "_has_aes")]
+            aes: hf.aes,
+            #[cfg(feature = "_has_crypto")]
+            crypto: hf.crypto,
+            #[cfg(feature = "_has_gpcrc")]
+            gpcrc: hf.gpcrc,
+            #[cfg(feature = "_has_usb")]
+            usb: hf.usb,
+            #[cfg(feature = "_has_ebi")]
+            ebi: hf.ebi,
+
+            lfaclk: lfa.lfaclk,
+            lfbclk: lfb.lfbclk,
+            #[cfg(feature = "chip-efr32xg1")]
+            lfeclk: lfe.lfeclk,
+            #[cfg(feature = "_has_rtc")]
+            rtc: lfa.rtc,
+            #[cfg(feature = "_has_rtcc")]
+            rtcc: lfe.rtcc,
+            letimer0: lfa.letimer0,
+            #[cfg(feature = "_has_lcd")]
+            lcd: lfa.lcd,
+            #[cfg(feature = "_has_lesense")]
+            lesense: lfa.lesense,
+            leuart0: lfb.leuart0,
+            #[cfg(feature = "_has_leuart1")]
+            leuart1: lfb.leuart1,
+            pcnt0: lfa.pcnt0,
+            #[cfg(feature = "_has_pcnt1")]
+            pcnt1: lfa.pcnt1,
+            #[cfg(feature = "_has_pcnt2")]
+            pcnt2: lfa.pcnt2,
+        })
+    }
+
+    /// Take out the GPIO clock gate.
+    ///
+    /// The GPIO clock does not depend on the clock configuration, and can thus be taken out
+    /// independently of any freezing. It is only available once.
+    pub fn take_gpio(&mut self) -> Option<GPIOClk> {
+        self.gpio.take()
+    }
+
+    /// Take out the clock outputs (CMU_OUT0 and CMU_OUT1).
+    ///
+    /// Like the GPIO clock gate, they can be taken out independently of any freezing, and are only
+    /// available once.
+    pub fn take_clock_outputs(&mut self) -> Option<ClockOutputs> {
+        self.clock_outputs.take()
+    }
+
+    /// Apply the configuration of the high frequency clock tree, and hand out its clocks.
+    ///
+    /// This starts the selected oscillator, waits for it to become ready, adjusts the flash wait
+    /// states and then switches over HFCLK.
+    pub fn freeze_hf(&mut self) -> Result<HFClocks, Error> {
+        if self.hf_frozen {
+            return Err(Error::AlreadyFrozen);
+        }
+
+        let hfclk = match self.hfclk {
+            HFClockSource::HFRCO(band) => match self.hfrco_calibration {
+                Some((_, frequency)) => frequency,
+                None => band.frequency(),
+            },
+            HFClockSource::HFXO(frequency) => frequency,
+        };
+        let hfcoreclk = Hertz(hfclk.0 / self.hfcoreclk_divisor as u32);
+        let hfperclk = Hertz(hfclk.0 / self.hfperclk_divisor as u32);
+
+        if let HFClockSource::HFXO(frequency) = self.hfclk {
+            self.start_hfxo(frequency)?;
+        }
+
+        // Until the new configuration is in place, the flash needs to be safe for any speed.
+        set_flash_wait_states(None);
+        self.set_hfle(hfcoreclk);
+
+        match self.hfclk {
+            HFClockSource::HFRCO(band) => self.select_hfrco(band),
+            HFClockSource::HFXO(_) => self.select_hfxo(),
+        }
+
+        self.set_divisors();
+
+        set_flash_wait_states(Some(hfcoreclk));
+
+        self.hf_frozen = true;
+
+        Ok(HFClocks {
+            hfcoreclk: HFCoreClk { frequency: hfcoreclk },
+            i2c0: I2C0Clk { frequency: hfperclk },
+            #[cfg(feature = "_has_i2c1")]
+            i2c1: I2C1Clk { frequency: hfperclk },
+            usart0: USART0Clk { frequency: hfperclk },
+            usart1: USART1Clk { frequency: hfperclk },
+            #[cfg(feature = "_has_usart2")]
+            usart2: USART2Clk { frequency: hfperclk },
+            #[cfg(feature = "_has_uart0")]
+            uart0: UART0Clk { frequency: hfperclk },
+            #[cfg(feature = "_has_uart1")]
+            uart1: UART1Clk { frequency: hfperclk },
+            timer0: TIMER0Clk { frequency: hfperclk },
+            timer1: TIMER1Clk { frequency: hfperclk },
             #[cfg(feature = "_has_timer2")]
-            timer2: TIMER2Clk { _private: () },
+            timer2: TIMER2Clk { frequency: hfperclk },
             #[cfg(feature = "_has_timer3")]
-            timer3: TIMER3Clk { _private: () },
+            timer3: TIMER3Clk { frequency: hfperclk },
+            #[cfg(feature = "_has_cryotimer")]
+            cryotimer: CRYOTIMERClk { _private: () },
+            acmp0: ACMP0Clk { frequency: hfperclk },
+            acmp1: ACMP1Clk { frequency: hfperclk },
+            #[cfg(feature = "_has_vcmp")]
+            vcmp: VCMPClk { frequency: hfperclk },
+            adc0: ADC0Clk { frequency: hfperclk },
+            #[cfg(feature = "_has_dac0")]
+            dac0: DAC0Clk { frequency: hfperclk },
+            #[cfg(feature = "_has_idac0")]
+            idac0: IDAC0Clk { frequency: hfperclk },
+            #[cfg(feature = "chip-efm32gg")]
+            prs: PRSClk { frequency: hfperclk },
+            #[cfg(feature = "chip-efr32xg1")]
+            prs: PRSClk { frequency: hfclk },
+            #[cfg(feature = "_has_dma")]
+            dma: DMAClk { frequency: hfcoreclk },
+            #[cfg(feature = "_has_ldma")]
+            ldma: LDMAClk { frequency: hfclk },
+            #[cfg(feature = "_has_aes")]
+            aes: AESClk { frequency: hfcoreclk },
+            #[cfg(feature = "_has_crypto")]
+            crypto: CRYPTOClk { frequency: hfclk },
+            #[cfg(feature = "_has_gpcrc")]
+            gpcrc: GPCRCClk { frequency: hfclk },
+            #[cfg(feature = "_has_usb")]
+            usb: USBClk { frequency: hfcoreclk },
+            #[cfg(feature = "_has_ebi")]
+            ebi: EBIClk { frequency: hfcoreclk },
+        })
+    }
+
+    /// Give back all the high frequency clocks, allowing the HF clock tree to be configured anew.
+    ///
+    /// The peripherals' clock gates are left as they are.
+    pub fn thaw_hf(&mut self, clocks: HFClocks) {
+        let _consumed = clocks;
+        self.hf_frozen = false;
+    }
+
+    /// Apply the source configuration of the LFACLK branch, and hand out its clocks.
+    pub fn freeze_lfa(&mut self) -> Result<LFAClocks, Error> {
+        if self.lfa_frozen {
+            return Err(Error::AlreadyFrozen);
         }
+
+        self.start_lf_source(self.lfa)?;
+        #[cfg(feature = "chip-efm32gg")]
+        self.register.lfclksel.modify(|_, w| match self.lfa {
+            // ULFRCO is selected on the EFM32GG by the "extended" bit on an otherwise disabled
+            // branch
+            LFClockSource::Disabled => w.lfa().disabled().lfae().clear_bit(),
+            LFClockSource::LFRCO => w.lfa().lfrco().lfae().clear_bit(),
+            LFClockSource::LFXO => w.lfa().lfxo().lfae().clear_bit(),
+            LFClockSource::ULFRCO => w.lfa().disabled().lfae().set_bit(),
+        });
+        #[cfg(feature = "chip-efr32xg1")]
+        self.register.lfaclksel.write(|w| match self.lfa {
+            LFClockSource::Disabled => w.lfa().disabled(),
+            LFClockSource::LFRCO => w.lfa().lfrco(),
+            LFClockSource::LFXO => w.lfa().lfxo(),
+            LFClockSource::ULFRCO => w.lfa().ulfrco(),
+        });
+
+        self.lfa_frozen = true;
+
+        let frequency = self.lfa.frequency();
+        Ok(LFAClocks {
+            lfaclk: LFAClk { frequency },
+            #[cfg(feature = "_has_rtc")]
+            rtc: RTCClk { frequency },
+            letimer0: LETIMER0Clk { frequency },
+            #[cfg(feature = "_has_lcd")]
+            lcd: LCDClk { frequency },
+            #[cfg(feature = "_has_lesense")]
+            lesense: LESENSEClk { frequency },
+            pcnt0: PCNT0Clk { frequency },
+            #[cfg(feature = "_has_pcnt1")]
+            pcnt1: PCNT1Clk { frequency },
+            #[cfg(feature = "_has_pcnt2")]
+            pcnt2: PCNT2Clk { frequency },
+        })
     }
-}
 
-pub struct HFCoreClk {
-    _private: (),
-}
+    /// Give back all the LFACLK clocks, allowing the branch to be configured anew.
+    ///
+    /// The peripherals' clock gates and the oscillators are left as they are.
+    pub fn thaw_lfa(&mut self, clocks: LFAClocks) {
+        let _consumed = clocks;
+        self.lfa_frozen = false;
+    }
 
-impl FrozenClock for HFCoreClk {
-    fn get_frequency(&self) -> Hertz {
-        // FIXME: This assumes that nothing has been changed since the reset; later we'll *want*
-        // that to change.
+    /// Apply the source configuration of the LFBCLK branch, and hand out its clocks.
+    pub fn freeze_lfb(&mut self) -> Result<LFBClocks, Error> {
+        if self.lfb_frozen {
+            return Err(Error::AlreadyFrozen);
+        }
+
+        self.start_lf_source(self.lfb)?;
+        #[cfg(feature = "chip-efm32gg")]
+        self.register.lfclksel.modify(|_, w| match self.lfb {
+            LFClockSource::Disabled => w.lfb().disabled().lfbe().clear_bit(),
+            LFClockSource::LFRCO => w.lfb().lfrco().lfbe().clear_bit(),
+            LFClockSource::LFXO => w.lfb().lfxo().lfbe().clear_bit(),
+            LFClockSource::ULFRCO => w.lfb().disabled().lfbe().set_bit(),
+        });
+        #[cfg(feature = "chip-efr32xg1")]
+        self.register.lfbclksel.write(|w| match self.lfb {
+            LFClockSource::Disabled => w.lfb().disabled(),
+            LFClockSource::LFRCO => w.lfb().lfrco(),
+            LFClockSource::LFXO => w.lfb().lfxo(),
+            LFClockSource::ULFRCO => w.lfb().ulfrco(),
+        });
+
+        self.lfb_frozen = true;
+
+        let frequency = self.lfb.frequency();
+        Ok(LFBClocks {
+            lfbclk: LFBClk { frequency },
+            leuart0: LEUART0Clk { frequency },
+            #[cfg(feature = "_has_leuart1")]
+            leuart1: LEUART1Clk { frequency },
+        })
+    }
+
+    /// Give back all the LFBCLK clocks, allowing the branch to be configured anew.
+    ///
+    /// The peripherals' clock gates and the oscillators are left as they are.
+    pub fn thaw_lfb(&mut self, clocks: LFBClocks) {
+        let _consumed = clocks;
+        self.lfb_frozen = false;
+    }
+
+    /// Apply the source configuration of the LFECLK branch, and hand out its clocks.
+    #[cfg(feature = "chip-efr32xg1")]
+    pub fn freeze_lfe(&mut self) -> Result<LFEClocks, Error> {
+        if self.lfe_frozen {
+            return Err(Error::AlreadyFrozen);
+        }
+
+        self.start_lf_source(self.lfe)?;
+        self.register.lfeclksel.write(|w| match self.lfe {
+            LFClockSource::Disabled => w.lfe().disabled(),
+            LFClockSource::LFRCO => w.lfe().lfrco(),
+            LFClockSource::LFXO => w.lfe().lfxo(),
+            LFClockSource::ULFRCO => w.lfe().ulfrco(),
+        });
+
+        self.lfe_frozen = true;
+
+        let frequency = self.lfe.frequency();
+        Ok(LFEClocks {
+            lfeclk: LFEClk { frequency },
+            #[cfg(feature = "_has_rtcc")]
+            rtcc: RTCCClk { frequency },
+        })
+    }
+
+    /// Give back all the LFECLK clocks, allowing the branch to be configured anew.
+    ///
+    /// The peripherals' clock gates and the oscillators are left as they are.
+    #[cfg(feature = "chip-efr32xg1")]
+    pub fn thaw_lfe(&mut self, clocks: LFEClocks) {
+        let _consumed = clocks;
+        self.lfe_frozen = false;
+    }
+
+    /// Make sure the oscillator behind a low frequency source is running, and that the low energy
+    /// peripherals can be accessed at all.
+    fn start_lf_source(&self, source: LFClockSource) -> Result<(), Error> {
+        match source {
+            LFClockSource::Disabled => return Ok(()),
+            LFClockSource::LFXO => {
+                self.register.oscencmd.write(|w| w.lfxoen().set_bit());
+                wait_ready(|| self.register.status.read().lfxordy().bit(), Error::LFXOTimeout)?;
+            }
+            LFClockSource::LFRCO => {
+                self.register.oscencmd.write(|w| w.lfrcoen().set_bit());
+                wait_ready(|| self.register.status.read().lfrcordy().bit(), Error::LFRCOTimeout)?;
+            }
+            // Always running
+            LFClockSource::ULFRCO => (),
+        }
+
+        // Without the LE interface clock, none of the low energy peripherals are accessible.
+        //
+        // Unsafe: This needs to be bit-banded as well, for the clock gates in the same register
+        // may be switched concurrently.
+        #[cfg(feature = "chip-efm32gg")]
+        unsafe { bitband::change_bit(&self.register.hfcoreclken0, LE_CLKEN_BIT, true) };
+        #[cfg(feature = "chip-efr32xg1")]
+        unsafe { bitband::change_bit(&self.register.hfbusclken0, LE_CLKEN_BIT, true) };
+
+        Ok(())
+    }
+
+    fn start_hfxo(&self, frequency: Hertz) -> Result<(), Error> {
+        // CTRL is shared with the clock outputs, see ClockOutputs
+        #[cfg(feature = "chip-efm32gg")]
+        cortex_m::interrupt::free(|_| {
+            self.register.ctrl.modify(|_, w| if frequency.0 > 32_000_000 {
+                w.hfxobufcur().boostabove32mhz()
+            } else {
+                w.hfxobufcur().boostupto32mhz()
+            });
+        });
+        #[cfg(feature = "chip-efr32xg1")]
+        let _ = frequency; // The EFR32 HFXO adjusts itself through its peak detection.
+
+        self.register.oscencmd.write(|w| w.hfxoen().set_bit());
+        wait_ready(|| self.register.status.read().hfxordy().bit(), Error::HFXOTimeout)
+    }
+
+    fn select_hfrco(&self, band: HFRCOBand) {
+        self.set_hfrco_band(band);
+        if let Some((tuning, _)) = self.hfrco_calibration {
+            self.set_tuning(TunableOscillator::HFRCO, tuning);
+        }
+
+        #[cfg(feature = "chip-efm32gg")]
+        self.register.cmd.write(|w| w.hfclksel().hfrco());
+        #[cfg(feature = "chip-efr32xg1")]
+        self.register.hfclksel.write(|w| w.hf().hfrco());
+    }
+
+    /// Start the HFRCO in the given band with its factory calibration.
+    #[cfg(feature = "chip-efm32gg")]
+    fn set_hfrco_band(&self, band: HFRCOBand) {
+        self.register.oscencmd.write(|w| w.hfrcoen().set_bit());
+        // The band enum is declared in the order of the register values
+        let tuning = crate::devinfo::hfrco_tuning(band as u8);
+        self.register.hfrcoctrl.modify(|_, w| unsafe { w.band().variant(band.variant()).tuning().bits(tuning) });
+        while !self.register.status.read().hfrcordy().bit() {}
+    }
+
+    /// Start the HFRCO in the given band with its factory calibration.
+    #[cfg(feature = "chip-efr32xg1")]
+    fn set_hfrco_band(&self, band: HFRCOBand) {
+        self.register.oscencmd.write(|w| w.hfrcoen().set_bit());
+        let ctrl = crate::devinfo::hfrco_ctrl(band.calibration_index());
+        self.register.hfrcoctrl.write(|w| unsafe { w.bits(ctrl) });
+        while self.register.syncbusy.read().hfrcobsy().bit() {}
+        while !self.register.status.read().hfrcordy().bit() {}
+    }
+
+    /// Trim the HFRCO to run as close as possible to the target frequency, measured against the
+    /// given reference, and return the frequency it was found to run at.
+    ///
+    /// This calibrates the band HFCLK is configured to run from; the HF clocks frozen later will
+    /// report the measured frequency. Configuring another band discards the calibration.
+    ///
+    /// The reference oscillator is started if it is not running already, and left running.
+    pub fn calibrate_hfrco(&mut self, target: Hertz, reference: CalibrationReference) -> Result<Hertz, Error> {
+        if self.hf_frozen {
+            return Err(Error::AlreadyFrozen);
+        }
+        let band = match self.hfclk {
+            HFClockSource::HFRCO(band) => band,
+            HFClockSource::HFXO(_) => return Err(Error::NotRunningFromHFRCO),
+        };
+
+        self.start_reference(reference)?;
+
+        // The HFRCO might be running the core right now, and is about to change
+        set_flash_wait_states(None);
+        self.set_hfrco_band(band);
+
+        let (tuning, frequency) = self.calibrate(TunableOscillator::HFRCO, target, reference);
+        self.hfrco_calibration = Some((tuning, frequency));
+        Ok(frequency)
+    }
+
+    /// Trim the AUXHFRCO to run as close as possible to the target frequency, measured against the
+    /// given reference, and return the frequency it was found to run at.
+    ///
+    /// The AUXHFRCO is started if it is not running already. None of the clocks handed out by
+    /// `split()` run from it (it drives eg. the ADC and the debug trace), so the returned frequency
+    /// is not reported anywhere else.
+    pub fn calibrate_auxhfrco(&mut self, target: Hertz, reference: CalibrationReference) -> Result<Hertz, Error> {
+        self.start_reference(reference)?;
+
+        self.register.oscencmd.write(|w| w.auxhfrcoen().set_bit());
+        while !self.register.status.read().auxhfrcordy().bit() {}
+
+        let (_, frequency) = self.calibrate(TunableOscillator::AUXHFRCO, target, reference);
+        Ok(frequency)
+    }
+
+    fn start_reference(&self, reference: CalibrationReference) -> Result<(), Error> {
+        match reference {
+            CalibrationReference::LFXO => self.start_lf_source(LFClockSource::LFXO),
+            CalibrationReference::HFXO(frequency) => self.start_hfxo(frequency),
+        }
+    }
+
+    /// Binary search for the tuning value that brings the oscillator closest to the target
+    /// frequency, and leave it configured.
+    ///
+    /// This relies on the frequency changing monotonically with the tuning value; the search runs
+    /// over steps of increasing frequency, which are mapped to tuning values depending on the
+    /// chip's TUNING_RAISES_FREQUENCY.
+    fn calibrate(&self, oscillator: TunableOscillator, target: Hertz, reference: CalibrationReference) -> (u8, Hertz) {
+        let tuning = |step: u8| if TUNING_RAISES_FREQUENCY { step } else { TUNING_MAX - step };
+        let measure = |step| {
+            self.set_tuning(oscillator, tuning(step));
+            self.measure(oscillator, target, reference)
+        };
+
+        let mut low = 0;
+        let mut high = TUNING_MAX;
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if measure(middle).0 < target.0 {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+
+        // `low` is the first step to reach the target; the one before might still be closer.
+        let mut best = (low, measure(low));
+        if low > 0 {
+            let below = measure(low - 1);
+            if target.0.saturating_sub(below.0) < (best.1).0.saturating_sub(target.0) {
+                best = (low - 1, below);
+            }
+        }
+
+        let best = (tuning(best.0), best.1);
+        self.set_tuning(oscillator, best.0);
+        best
+    }
+
+    fn set_tuning(&self, oscillator: TunableOscillator, tuning: u8) {
+        // Unsafe: The tuning values are limited to TUNING_MAX by the callers
+        match oscillator {
+            TunableOscillator::HFRCO => {
+                self.register.hfrcoctrl.modify(|_, w| unsafe { w.tuning().bits(tuning) });
+                #[cfg(feature = "chip-efr32xg1")]
+                while self.register.syncbusy.read().hfrcobsy().bit() {}
+            }
+            TunableOscillator::AUXHFRCO => {
+                self.register.auxhfrcoctrl.modify(|_, w| unsafe { w.tuning().bits(tuning) });
+                #[cfg(feature = "chip-efr32xg1")]
+                while self.register.syncbusy.read().auxhfrcobsy().bit() {}
+            }
+        }
+    }
+
+    /// Measure the frequency of an oscillator against the reference using the calibration counter.
+    ///
+    /// The down counter is run from the reference for a number of cycles that makes the up counter
+    /// (clocked by the oscillator) count to around 2^16 if the oscillator is at the expected
+    /// frequency, which is plenty of precision for the tuning steps and leaves room in the 20 bit
+    /// counter for oscillators that run fast.
+    fn measure(&self, oscillator: TunableOscillator, expected: Hertz, reference: CalibrationReference) -> Hertz {
+        let reference_frequency = reference.frequency().0 as u64;
+        let top = (reference_frequency << 16) / expected.0.max(1) as u64;
+        let top = top.clamp(1, 0xf_ffff) as u32;
+
+        self.register.calctrl.write(|w| {
+            match oscillator {
+                TunableOscillator::HFRCO => w.upsel().hfrco(),
+                TunableOscillator::AUXHFRCO => w.upsel().auxhfrco(),
+            };
+            match reference {
+                CalibrationReference::LFXO => w.downsel().lfxo(),
+                CalibrationReference::HFXO(_) => w.downsel().hfxo(),
+            }.cont().clear_bit()
+        });
+        // Unsafe: top is limited to the 20 bits of the register
+        self.register.calcnt.write(|w| unsafe { w.calcnt().bits(top) });
+        self.register.cmd.write(|w| w.calstart().set_bit());
+
+        #[cfg(feature = "chip-efm32gg")]
+        while self.register.status.read().calbsy().bit() {}
+        #[cfg(feature = "chip-efr32xg1")]
+        while !self.register.status.read().calrdy().bit() {}
+
+        let count = self.register.calcnt.read().calcnt().bits() as u64;
+        Hertz((count * reference_frequency / top as u64) as u32)
+    }
+
+    fn select_hfxo(&self) {
+        #[cfg(feature = "chip-efm32gg")]
+        self.register.cmd.write(|w| w.hfclksel().hfxo());
+        #[cfg(feature = "chip-efr32xg1")]
+        self.register.hfclksel.write(|w| w.hf().hfxo());
+    }
+
+    /// Configure the access to the low energy peripherals' bus, which can not run faster than
+    /// 32MHz.
+    fn set_hfle(&self, hfcoreclk: Hertz) {
+        let fast = hfcoreclk.0 > 32_000_000;
+        #[cfg(feature = "chip-efm32gg")]
+        {
+            self.register.hfcoreclkdiv.modify(|_, w| w.hfcoreclklediv().bit(fast));
+            cortex_m::interrupt::free(|_| self.register.ctrl.modify(|_, w| w.hfle().bit(fast)));
+        }
+        #[cfg(feature = "chip-efr32xg1")]
+        {
+            self.register.hfpresc.modify(|_, w| if fast {
+                w.hfclklepresc().div4()
+            } else {
+                w.hfclklepresc().div2()
+            });
+            cortex_m::interrupt::free(|_| self.register.ctrl.modify(|_, w| w.wshfle().bit(fast)));
+        }
+    }
+
+    fn set_divisors(&self) {
         #[cfg(feature = "chip-efm32gg")]
         {
-            Hertz(14_000_000)
+            // Unsafe: The divisors were checked to be powers of two not exceeding 512
+            let core = self.hfcoreclk_divisor.trailing_zeros() as u8;
+            self.register.hfcoreclkdiv.modify(|_, w| unsafe { w.hfcoreclkdiv().bits(core) });
+            let per = self.hfperclk_divisor.trailing_zeros() as u8;
+            self.register.hfperclkdiv.modify(|_, w| unsafe { w.hfperclkdiv().bits(per) });
         }
         #[cfg(feature = "chip-efr32xg1")]
         {
-            Hertz(19_000_000)
+            // Unsafe: The divisors were checked to be in the range of the 9-bit register
+            let core = self.hfcoreclk_divisor - 1;
+            self.register.hfcorepresc.modify(|_, w| unsafe { w.presc().bits(core) });
+            let per = self.hfperclk_divisor - 1;
+            self.register.hfperpresc.modify(|_, w| unsafe { w.presc().bits(per) });
+        }
+    }
+}
+
+/// Token for the CMU_OUT0 clock output, obtained from `ClockOutputs`
+pub struct ClkOut0 {
+    _private: (),
+}
+/// Token for the CMU_OUT1 clock output, obtained from `ClockOutputs`
+pub struct ClkOut1 {
+    _private: (),
+}
+
+/// Clocks selectable for the CMU_OUT0 clock output
+#[cfg(feature = "chip-efm32gg")]
+#[derive(Clone, Copy)]
+pub enum ClkOut0Source {
+    HFRCO,
+    HFXO,
+    /// HFCLK divided by 2
+    HFCLK2,
+    /// HFCLK divided by 4
+    HFCLK4,
+    /// HFCLK divided by 8
+    HFCLK8,
+    /// HFCLK divided by 16
+    HFCLK16,
+    ULFRCO,
+    AUXHFRCO,
+}
+
+#[cfg(feature = "chip-efm32gg")]
+impl ClkOut0Source {
+    fn variant(self) -> registers::cmu::ctrl::CLKOUTSEL0W {
+        use registers::cmu::ctrl::CLKOUTSEL0W;
+        match self {
+            ClkOut0Source::HFRCO => CLKOUTSEL0W::HFRCO,
+            ClkOut0Source::HFXO => CLKOUTSEL0W::HFXO,
+            ClkOut0Source::HFCLK2 => CLKOUTSEL0W::HFCLK2,
+            ClkOut0Source::HFCLK4 => CLKOUTSEL0W::HFCLK4,
+            ClkOut0Source::HFCLK8 => CLKOUTSEL0W::HFCLK8,
+            ClkOut0Source::HFCLK16 => CLKOUTSEL0W::HFCLK16,
+            ClkOut0Source::ULFRCO => CLKOUTSEL0W::ULFRCO,
+            ClkOut0Source::AUXHFRCO => CLKOUTSEL0W::AUXHFRCO,
         }
     }
 }
+
+/// Clocks selectable for the CMU_OUT1 clock output
+///
+/// The variants ending in `Q` are the respective oscillator outputs qualified by the oscillator's
+/// ready signal.
+#[cfg(feature = "chip-efm32gg")]
+#[derive(Clone, Copy)]
+pub enum ClkOut1Source {
+    LFRCO,
+    LFXO,
+    HFCLK,
+    LFXOQ,
+    HFXOQ,
+    LFRCOQ,
+    HFRCOQ,
+    AUXHFRCOQ,
+}
+
+#[cfg(feature = "chip-efm32gg")]
+impl ClkOut1Source {
+    fn variant(self) -> registers::cmu::ctrl::CLKOUTSEL1W {
+        use registers::cmu::ctrl::CLKOUTSEL1W;
+        match self {
+            ClkOut1Source::LFRCO => CLKOUTSEL1W::LFRCO,
+            ClkOut1Source::LFXO => CLKOUTSEL1W::LFXO,
+            ClkOut1Source::HFCLK => CLKOUTSEL1W::HFCLK,
+            ClkOut1Source::LFXOQ => CLKOUTSEL1W::LFXOQ,
+            ClkOut1Source::HFXOQ => CLKOUTSEL1W::HFXOQ,
+            ClkOut1Source::LFRCOQ => CLKOUTSEL1W::LFRCOQ,
+            ClkOut1Source::HFRCOQ => CLKOUTSEL1W::HFRCOQ,
+            ClkOut1Source::AUXHFRCOQ => CLKOUTSEL1W::AUXHFRCOQ,
+        }
+    }
+}
+
+/// Clocks selectable for the CMU_OUT0 and CMU_OUT1 clock outputs
+///
+/// The variants ending in `Q` are the respective oscillator outputs qualified by the oscillator's
+/// ready signal.
+#[cfg(feature = "chip-efr32xg1")]
+#[derive(Clone, Copy)]
+pub enum ClkOutSource {
+    ULFRCO,
+    LFRCO,
+    LFXO,
+    HFXO,
+    HFEXPCLK,
+    ULFRCOQ,
+    LFRCOQ,
+    LFXOQ,
+    HFRCOQ,
+    AUXHFRCOQ,
+    HFXOQ,
+    HFSRCCLK,
+}
+
+#[cfg(feature = "chip-efr32xg1")]
+pub use self::ClkOutSource as ClkOut0Source;
+#[cfg(feature = "chip-efr32xg1")]
+pub use self::ClkOutSource as ClkOut1Source;
+
+#[cfg(feature = "chip-efr32xg1")]
+macro_rules! clkoutsource_variant {
+    ($fn: ident, $W: ident) => {
+        fn $fn(self) -> registers::cmu::ctrl::$W {
+            use registers::cmu::ctrl::$W;
+            match self {
+                ClkOutSource::ULFRCO => $W::ULFRCO,
+                ClkOutSource::LFRCO => $W::LFRCO,
+                ClkOutSource::LFXO => $W::LFXO,
+                ClkOutSource::HFXO => $W::HFXO,
+                ClkOutSource::HFEXPCLK => $W::HFEXPCLK,
+                ClkOutSource::ULFRCOQ => $W::ULFRCOQ,
+                ClkOutSource::LFRCOQ => $W::LFRCOQ,
+                ClkOutSource::LFXOQ => $W::LFXOQ,
+                ClkOutSource::HFRCOQ => $W::HFRCOQ,
+                ClkOutSource::AUXHFRCOQ => $W::AUXHFRCOQ,
+                ClkOutSource::HFXOQ => $W::HFXOQ,
+                ClkOutSource::HFSRCCLK => $W::HFSRCCLK,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "chip-efr32xg1")]
+impl ClkOutSource {
+    clkoutsource_variant!(variant0, CLKOUTSEL0W);
+    clkoutsource_variant!(variant1, CLKOUTSEL1W);
+}
+
+/// The CMU_OUT0 and CMU_OUT1 clock outputs, obtained from `Clocks` or
+/// `Cmu::take_clock_outputs()`.
+pub struct ClockOutputs {
+    pub clkout0: ClkOut0,
+    pub clkout1: ClkOut1,
+}
+
+/// A clock output that drives a GPIO pin, obtained by `ClkOut0::route()` or `ClkOut1::route()`.
+pub struct RoutedClockOutput<Output, Pin> {
+    output: Output,
+    pin: Pin,
+}
+
+impl<O, P> RoutedClockOutput<O, P> where
+    P: crate::routing::HasLocForFunction<Cmu, O>,
+{
+    /// Stop driving the pin with the clock, and give back the output and the pin.
+    ///
+    /// The clock selection is left as it is, as it is immaterial once the pin is disabled.
+    pub fn unroute(self) -> (O, P) {
+        unsafe { P::deconfigure() };
+        (self.output, self.pin)
+    }
+}
+
+impl ClkOut0 {
+    /// Output one of the clocks selectable for CMU_OUT0 on the given pin.
+    ///
+    /// The routing location is derived from the pin. On EFM32GG, both clock outputs share a single
+    /// location, so this panics if the pin would conflict with an already routed CMU_OUT1.
+    ///
+    /// Some clocks (eg. HFXO) are only output while they are running, which makes this usable for
+    /// checking oscillator startup with a scope.
+    pub fn route<P>(self, clock: ClkOut0Source, pin: P) -> RoutedClockOutput<Self, P> where
+        P: crate::routing::HasLocForFunction<Cmu, ClkOut0>,
+    {
+        #[cfg(feature = "chip-efm32gg")]
+        let clock = clock.variant();
+        #[cfg(feature = "chip-efr32xg1")]
+        let clock = clock.variant0();
+        cortex_m::interrupt::free(|_| {
+            // Unsafe: The token owns the CLKOUTSEL0 field of CTRL; all other writes to CTRL
+            // (including the Cmu's) happen in critical sections, so the read-modify-write can not
+            // interleave with them.
+            let cmu = unsafe { &*registers::CMU::ptr() };
+            cmu.ctrl.modify(|_, w| w.clkoutsel0().variant(clock));
+        });
+        unsafe { P::configure() };
+        RoutedClockOutput { output: self, pin }
+    }
+}
+
+impl ClkOut1 {
+    /// Output one of the clocks selectable for CMU_OUT1 on the given pin.
+    ///
+    /// See `ClkOut0::route()` for details.
+    pub fn route<P>(self, clock: ClkOut1Source, pin: P) -> RoutedClockOutput<Self, P> where
+        P: crate::routing::HasLocForFunction<Cmu, ClkOut1>,
+    {
+        #[cfg(feature = "chip-efm32gg")]
+        let clock = clock.variant();
+        #[cfg(feature = "chip-efr32xg1")]
+        let clock = clock.variant1();
+        cortex_m::interrupt::free(|_| {
+            // Unsafe: See ClkOut0::route
+            let cmu = unsafe { &*registers::CMU::ptr() };
+            cmu.ctrl.modify(|_, w| w.clkoutsel1().variant(clock));
+        });
+        unsafe { P::configure() };
+        RoutedClockOutput { output: self, pin }
+    }
+}
+
+/// Poll an oscillator's ready flag until it is set, or give up after OSCILLATOR_TIMEOUT attempts.
+fn wait_ready(ready: impl Fn() -> bool, error: Error) -> Result<(), Error> {
+    for _ in 0..OSCILLATOR_TIMEOUT {
+        if ready() {
+            return Ok(());
+        }
+    }
+    Err(error)
+}
+
+fn divisor_is_valid(divisor: u16) -> bool {
+    #[cfg(feature = "chip-efm32gg")]
+    {
+        divisor.is_power_of_two() && divisor <= 512
+    }
+    #[cfg(feature = "chip-efr32xg1")]
+    {
+        (1..=512).contains(&divisor)
+    }
+}
+
+/// Adjust the flash read mode to the given HFCORECLK frequency, or to the slowest (and thus safest)
+/// mode if None is given.
+fn set_flash_wait_states(hfcoreclk: Option<Hertz>) {
+    let frequency = hfcoreclk.map(|f| f.0).unwrap_or(u32::MAX);
+
+    // UNSAFE FIXME: This should rather be done on an owned MSC, but until there is a need to
+    // abstract the MSC, the CMU is the only user of the READCTRL register.
+    let msc = unsafe { &*registers::MSC::ptr() };
+
+    #[cfg(feature = "chip-efm32gg")]
+    msc.readctrl.modify(|_, w| match frequency {
+        0..=16_000_000 => w.mode().ws0(),
+        16_000_001..=32_000_000 => w.mode().ws1(),
+        _ => w.mode().ws2(),
+    });
+    #[cfg(feature = "chip-efr32xg1")]
+    msc.readctrl.modify(|_, w| match frequency {
+        0..=26_000_000 => w.mode().ws0(),
+        _ => w.mode().ws1(),
+    });
+}
+
+/// Oscillator state that is lost when entering EM2 or EM3, saved by the EMU before going to sleep.
+pub(crate) struct SleepState {
+    hfxo_selected: bool,
+    lfxo_enabled: bool,
+    lfrco_enabled: bool,
+}
+
+impl SleepState {
+    pub(crate) fn save() -> Self {
+        // UNSAFE FIXME: The Cmu is usually consumed by the time the device goes to sleep; this
+        // only reads the status, and writes to oscillator enable and HFCLK selection registers
+        // that are not touched any more once the clocks are frozen.
+        let cmu = unsafe { &*registers::CMU::ptr() };
+        let status = cmu.status.read();
+        SleepState {
+            #[cfg(feature = "chip-efm32gg")]
+            hfxo_selected: status.hfxosel().bit(),
+            #[cfg(feature = "chip-efr32xg1")]
+            hfxo_selected: cmu.hfclkstatus.read().selected().is_hfxo(),
+            lfxo_enabled: status.lfxoens().bit(),
+            lfrco_enabled: status.lfrcoens().bit(),
+        }
+    }
+
+    /// Turn off the LFXO and LFRCO, so that deep sleep enters EM3 rather than EM2.
+    pub(crate) fn stop_lf_oscillators(&self) {
+        // UNSAFE FIXME as in save()
+        let cmu = unsafe { &*registers::CMU::ptr() };
+        cmu.oscencmd.write(|w| w.lfxodis().set_bit().lfrcodis().set_bit());
+    }
+
+    /// Restart the oscillators that were running before sleep, and switch HFCLK back to the HFXO
+    /// if it was the source, so that the frozen clocks' frequencies are valid again.
+    pub(crate) fn restore(&self) -> Result<(), Error> {
+        // UNSAFE FIXME as in save()
+        let cmu = unsafe { &*registers::CMU::ptr() };
+
+        if self.lfxo_enabled {
+            cmu.oscencmd.write(|w| w.lfxoen().set_bit());
+        }
+        if self.lfrco_enabled {
+            cmu.oscencmd.write(|w| w.lfrcoen().set_bit());
+        }
+
+        if self.hfxo_selected {
+            cmu.oscencmd.write(|w| w.hfxoen().set_bit());
+            wait_ready(|| cmu.status.read().hfxordy().bit(), Error::HFXOTimeout)?;
+            #[cfg(feature = "chip-efm32gg")]
+            cmu.cmd.write(|w| w.hfclksel().hfxo());
+            #[cfg(feature = "chip-efr32xg1")]
+            cmu.hfclksel.write(|w| w.hf().hfxo());
+        }
+
+        if self.lfxo_enabled {
+            wait_ready(|| cmu.status.read().lfxordy().bit(), Error::LFXOTimeout)?;
+        }
+        if self.lfrco_enabled {
+            wait_ready(|| cmu.status.read().lfrcordy().bit(), Error::LFRCOTimeout)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct HFCoreClk {
+    frequency: Hertz,
+}
+
+impl FrozenClock for HFCoreClk {
+    fn get_frequency(&self) -> Hertz {
+        self.frequency
+    }
+}
+
+pub struct LFAClk {
+    frequency: Hertz,
+}
+
+impl FrozenClock for LFAClk {
+    fn get_frequency(&self) -> Hertz {
+        self.frequency
+    }
+}
+
+pub struct LFBClk {
+    frequency: Hertz,
+}
+
+impl FrozenClock for LFBClk {
+    fn get_frequency(&self) -> Hertz {
+        self.frequency
+    }
+}
+
+#[cfg(feature = "chip-efr32xg1")]
+pub struct LFEClk {
+    frequency: Hertz,
+}
+
+#[cfg(feature = "chip-efr32xg1")]
+impl FrozenClock for LFEClk {
+    fn get_frequency(&self) -> Hertz {
+        self.frequency
+    }
+}
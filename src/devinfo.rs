@@ -0,0 +1,32 @@
+//! Device information page
+//!
+//! The DI page is a read-only flash area that holds factory calibration values. It is not described
+//! in the SVD files and thus not part of the register crates, so the values are read from the
+//! addresses given in the reference manuals.
+
+use core::ptr::read_volatile;
+
+const DEVINFO_BASE: usize = 0x0FE0_81B0;
+
+fn read(offset: usize) -> u32 {
+    // Unsafe: This is a read-only area that is present on all devices
+    unsafe { read_volatile((DEVINFO_BASE + offset) as *const u32) }
+}
+
+/// Factory calibrated HFRCO TUNING value for a band, given by its index in the BAND field.
+#[cfg(feature = "chip-efm32gg")]
+pub(crate) fn hfrco_tuning(band: u8) -> u8 {
+    // HFRCOCAL0 holds bands 0 to 3, HFRCOCAL1 holds bands 4 and 5, one per byte.
+    let word = match band {
+        0..=3 => read(0x2c),
+        _ => read(0x30),
+    };
+    (word >> (8 * (band % 4))) as u8
+}
+
+/// Factory calibrated HFRCOCTRL register value for the band indicated by the HFRCOCALn register
+/// number.
+#[cfg(feature = "chip-efr32xg1")]
+pub(crate) fn hfrco_ctrl(n: u8) -> u32 {
+    read(0x80 + 4 * n as usize)
+}
@@ -0,0 +1,250 @@
+//! EMU (Energy Management Unit)
+//!
+//! This allows putting the device into its sleep modes, EM1 to EM4.
+//!
+//! Entering EM2 or EM3 stops the high frequency oscillators, and the device wakes up running from
+//! the HFRCO. The `enter_em2` and `enter_em3` methods restore whichever HFCLK source was
+//! configured in the CMU before they return, so frozen clocks (and everything that was built
+//! using their frequencies) stay valid.
+//!
+//! For the same to hold inside the interrupt handler that woke the device, the sleep methods
+//! should be called from inside a critical section (eg. in `cortex_m::interrupt::free`): The
+//! pending interrupt still wakes the device, but its handler only runs after the critical section
+//! is left, and thus after the clocks are restored.
+//!
+//! Drivers for peripherals that stop working in deeper sleep modes (eg. a timer that generates a
+//! PWM signal, which needs HFPERCLK and thus stops in EM2) register themselves as blockers while
+//! they are in use. Trying to enter an energy mode they do not work in then returns an error
+//! listing them, rather than silently stopping the peripheral.
+
+use core::convert::Infallible;
+use core::fmt;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use cortex_m::peripheral::SCB;
+
+use registers;
+
+use crate::cmu;
+
+/// The sleep modes of the device, in the order of increasing depth
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum EnergyMode {
+    EM1,
+    EM2,
+    EM3,
+    EM4,
+}
+
+/// A driver that can keep the device from entering deeper energy modes while it is in use
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Blocker {
+    Timer0,
+    Timer1,
+    #[cfg(feature = "_has_timer2")]
+    Timer2,
+    #[cfg(feature = "_has_timer3")]
+    Timer3,
+    I2C0,
+}
+
+const ALL_BLOCKERS: &[Blocker] = &[
+    Blocker::Timer0,
+    Blocker::Timer1,
+    #[cfg(feature = "_has_timer2")]
+    Blocker::Timer2,
+    #[cfg(feature = "_has_timer3")]
+    Blocker::Timer3,
+    Blocker::I2C0,
+];
+
+impl Blocker {
+    /// The deepest energy mode in which the driver's peripheral keeps working
+    pub fn lowest_energy_mode(&self) -> EnergyMode {
+        match self {
+            // All of these run from HFPERCLK
+            Blocker::Timer0 | Blocker::Timer1 | Blocker::I2C0 => EnergyMode::EM1,
+            #[cfg(feature = "_has_timer2")]
+            Blocker::Timer2 => EnergyMode::EM1,
+            #[cfg(feature = "_has_timer3")]
+            Blocker::Timer3 => EnergyMode::EM1,
+        }
+    }
+
+    fn mask(&self) -> usize {
+        1 << (*self as usize)
+    }
+}
+
+/// Bit set of the currently active Blockers
+static ACTIVE_BLOCKERS: AtomicUsize = AtomicUsize::new(0);
+
+/// Mark a driver as active, keeping the device out of energy modes its peripheral does not work
+/// in.
+pub(crate) fn block(blocker: Blocker) {
+    ACTIVE_BLOCKERS.fetch_or(blocker.mask(), Ordering::SeqCst);
+}
+
+/// Mark a driver as released again.
+pub(crate) fn unblock(blocker: Blocker) {
+    ACTIVE_BLOCKERS.fetch_and(!blocker.mask(), Ordering::SeqCst);
+}
+
+/// A set of drivers that keep the device from entering an energy mode
+#[derive(Clone, Copy, PartialEq)]
+pub struct Blockers(usize);
+
+impl Blockers {
+    /// The active drivers that do not work in the given energy mode
+    fn active_for(mode: EnergyMode) -> Self {
+        let active = ACTIVE_BLOCKERS.load(Ordering::SeqCst);
+        Blockers(ALL_BLOCKERS.iter()
+            .filter(|b| active & b.mask() != 0 && b.lowest_energy_mode() < mode)
+            .fold(0, |acc, b| acc | b.mask()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, blocker: Blocker) -> bool {
+        self.0 & blocker.mask() != 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Blocker> {
+        let bits = self.0;
+        ALL_BLOCKERS.iter().cloned().filter(move |b| bits & b.mask() != 0)
+    }
+}
+
+impl fmt::Debug for Blockers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// Error conditions when entering a sleep mode
+#[derive(Debug)]
+pub enum Error {
+    /// The energy mode was not entered because active drivers would stop working in it
+    Blocked(Blockers),
+    /// The device slept, but the clocks could not be restored on wakeup
+    Clock(cmu::Error),
+}
+
+impl From<cmu::Error> for Error {
+    fn from(e: cmu::Error) -> Self {
+        Error::Clock(e)
+    }
+}
+
+pub trait EMUExt {
+    fn constrain(self) -> Emu;
+}
+
+impl EMUExt for registers::EMU {
+    fn constrain(self) -> Emu {
+        Emu { register: self }
+    }
+}
+
+pub struct Emu {
+    register: registers::EMU,
+}
+
+impl Emu {
+    /// Sleep in EM1 until an interrupt occurs.
+    ///
+    /// Only the core is stopped; all clocks and peripherals keep running.
+    pub fn enter_em1(&mut self, scb: &mut SCB) {
+        scb.clear_sleepdeep();
+        cortex_m::asm::wfi();
+    }
+
+    /// Sleep in EM2 until an interrupt occurs.
+    ///
+    /// The high frequency clocks are stopped, while the low frequency clocks keep running
+    /// peripherals like the RTC, LETIMER or LEUART.
+    ///
+    /// The device does not go to sleep if any active driver would stop working in EM2. An error is
+    /// also returned if the HFXO was the HFCLK source but can not be restarted; the device is then
+    /// still running from the HFRCO.
+    pub fn enter_em2(&mut self, scb: &mut SCB) -> Result<(), Error> {
+        check_blockers(EnergyMode::EM2)?;
+        let state = cmu::SleepState::save();
+        self.deep_sleep(scb);
+        Ok(state.restore()?)
+    }
+
+    /// Sleep in EM3 until an interrupt occurs.
+    ///
+    /// This is like EM2, but the LFXO and LFRCO are stopped as well, so only the ULFRCO keeps
+    /// running. They are restarted along with the high frequency clocks on wakeup.
+    pub fn enter_em3(&mut self, scb: &mut SCB) -> Result<(), Error> {
+        check_blockers(EnergyMode::EM3)?;
+        let state = cmu::SleepState::save();
+        state.stop_lf_oscillators();
+        self.deep_sleep(scb);
+        Ok(state.restore()?)
+    }
+
+    /// Enter EM4, from which the device only wakes up through a reset.
+    ///
+    /// If any active driver would stop working in EM4, the device does not go to sleep and the
+    /// Emu is returned along with the blockers.
+    pub fn enter_em4(self) -> Result<Infallible, (Self, Blockers)> {
+        let blockers = Blockers::active_for(EnergyMode::EM4);
+        if !blockers.is_empty() {
+            return Err((self, blockers));
+        }
+
+        // The entry sequence must not be interrupted by other writes to the register, so the values
+        // are precomputed and written from inside a critical section.
+        cortex_m::interrupt::free(|_| {
+            #[cfg(feature = "chip-efm32gg")]
+            {
+                let base = self.register.ctrl.read().bits() & !(0b11 << 2);
+                for &step in [2, 3, 2, 3, 2, 3, 2, 3, 2].iter() {
+                    self.register.ctrl.write(|w| unsafe { w.bits(base).em4ctrl().bits(step) });
+                }
+            }
+            #[cfg(feature = "chip-efr32xg1")]
+            {
+                let base = self.register.em4ctrl.read().bits() & !(0b11 << 16);
+                for &step in [2, 3, 2, 3, 2, 3, 2, 3, 2].iter() {
+                    self.register.em4ctrl.write(|w| unsafe { w.bits(base).em4entry().bits(step) });
+                }
+            }
+        });
+
+        // Not reached; EM4 is left through a reset.
+        loop {
+            cortex_m::asm::wfi();
+        }
+    }
+
+    /// Keep the GPIO pins' state while in EM4; see `gpio::EM4::set_retention`.
+    #[cfg(feature = "chip-efr32xg1")]
+    pub(crate) fn set_em4_io_retention(&mut self, retain: bool) {
+        self.register.em4ctrl.modify(|_, w| if retain {
+            w.em4ioretmode().em4exit()
+        } else {
+            w.em4ioretmode().disable()
+        });
+    }
+
+    fn deep_sleep(&mut self, scb: &mut SCB) {
+        scb.set_sleepdeep();
+        cortex_m::asm::wfi();
+        scb.clear_sleepdeep();
+    }
+}
+
+fn check_blockers(mode: EnergyMode) -> Result<(), Error> {
+    let blockers = Blockers::active_for(mode);
+    if blockers.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Blocked(blockers))
+    }
+}
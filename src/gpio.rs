@@ -1,22 +1,66 @@
 //! GPIO (general purpose input/output), mapped to embedded_hal::digital
 //!
 //! This implements only what is minimally essential to make input or output pins out of the GPIO
-//! register block. Other drive modes could be added with relative ease (eg. Wired-And, Wired-Or,
-//! input with pull-up/-down), others (eg. the per-bank drive strength, EM4 wakeup) will need
-//! additional mechanisms, and some (eg. clearing the configuration lock) might need changes to the
-//! whole model if at all desired.
+//! register block. Other drive modes could be added with relative ease (eg. Wired-Or).
+//!
+//! The EM4 wakeup configuration and the per-port drive settings, which are shared by several pins,
+//! are accessible through the `em4` and `ports` members of the split Pins.
+//!
+//! External interrupts are configured by combining an input pin with the interrupt line of the same
+//! number, which is taken out of the `exti` member of the split Pins. As there is only one of each
+//! line, no two pins can be configured for the same line (eg. PA3 and PB3).
+//!
+//! Pins of the same port can be combined into a group (eg. `PAGroup`) to write or read them all at
+//! once, for example to drive a parallel data bus without glitches between the bits.
+//!
+//! The pins of the debug interface (PF0 and PF1, and on EFR32 also PF2 and PF3) are not handed out
+//! with the other pins, but only after `disable_debug_pins()` was called on the `debug` member of
+//! the split Pins. Once pins are configured, the `lock` member can seal them against further
+//! configuration changes.
 
 use registers;
 
 use super::cmu;
+use super::emu;
 use core::marker::PhantomData;
+use core::ptr::{read_volatile, write_volatile};
 use embedded_hal::digital;
 
 use crate::bitband;
 
 pub struct Disabled {}
 pub struct Output {}
+/// Open drain (wired "and") output, whose actual level can be read back as an input
+pub struct OpenDrain {}
 pub struct Input {}
+/// Input with the internal pull-up resistor enabled
+pub struct InputPullUp {}
+/// Input with the internal pull-down resistor enabled
+pub struct InputPullDown {}
+/// Input with the glitch suppression filter enabled
+pub struct InputFilter {}
+/// Input with the internal pull-up resistor and the glitch suppression filter enabled
+pub struct InputPullUpFilter {}
+/// Input with the internal pull-down resistor and the glitch suppression filter enabled
+pub struct InputPullDownFilter {}
+/// Disabled for digital use (with the input buffer turned off), to be used by an analog peripheral
+#[cfg(feature = "chip-efm32gg")]
+pub struct Analog {}
+
+/// Marker for all the modes in which a pin can be driven
+pub trait OutputMode {}
+impl OutputMode for Output {}
+impl OutputMode for OpenDrain {}
+
+/// Marker for all the modes in which a pin can be read
+pub trait InputMode {}
+impl InputMode for OpenDrain {}
+impl InputMode for Input {}
+impl InputMode for InputPullUp {}
+impl InputMode for InputPullDown {}
+impl InputMode for InputFilter {}
+impl InputMode for InputPullUpFilter {}
+impl InputMode for InputPullDownFilter {}
 
 pub trait GPIOExt {
     fn split(self, gpioclk: cmu::GPIOClk) -> Pins;
@@ -31,18 +75,27 @@ pub trait GPIOExt {
 /// traits; for example, PA0<T> implements EFM32Pin for all T, and configurin it as an output pin
 /// with `.as_output()` makes it into a PA0<Output> which implements digital::OutputPin.
 ///
-/// Currently, not all information about a pin is encoded in its type (For example, as_output and
-/// as_opendrain produce the same type), that may change just by adding an InputOutput
-/// configuration state that has implements both input and output traits (as it makes sense to both
-/// write to and read an open drain pin), or may even go as far as to encode the full pin
-/// configuration.
+/// Currently, not all information about a pin is encoded in its type (For example, the open drain
+/// variants with and without pull-up all produce the same OpenDrain type, which implements both
+/// input and output traits); that may go as far as to encode the full pin configuration.
 pub trait EFM32Pin {
     type Disabled;
     #[cfg(not(feature = "unproven"))]
     type Output: digital::OutputPin;
     #[cfg(feature = "unproven")]
     type Output: digital::OutputPin + digital::StatefulOutputPin;
+    #[cfg(not(feature = "unproven"))]
+    type OpenDrain: digital::OutputPin + digital::InputPin;
+    #[cfg(feature = "unproven")]
+    type OpenDrain: digital::OutputPin + digital::StatefulOutputPin + digital::InputPin;
     type Input: digital::InputPin;
+    type InputPullUp: digital::InputPin;
+    type InputPullDown: digital::InputPin;
+    type InputFilter: digital::InputPin;
+    type InputPullUpFilter: digital::InputPin;
+    type InputPullDownFilter: digital::InputPin;
+    #[cfg(feature = "chip-efm32gg")]
+    type Analog;
 
     /// Convert the pin into an output pin. The original pin, however configured, is consumed, the
     /// hardware configuration changed to drive high or low, and returned as a pin that implements
@@ -51,23 +104,1045 @@ pub trait EFM32Pin {
 
     /// Convert the pin into an open drain (wired "and") pin. The original pin, however configured,
     /// is consumed, the hardware configuration changed to only drive low, and returned as a pin
-    /// that implements embedded_hal::digital::OutputPin (and should later implement InputPin too,
-    /// buit that needs some thinking-through anyway w/rt how much of the configure state should be
-    /// in the typ).
-    fn as_opendrain(self: Self) -> Self::Output;
+    /// that implements embedded_hal::digital::OutputPin, and InputPin to read back the level on the
+    /// line (eg. for bit-banging bus protocols).
+    fn as_opendrain(self: Self) -> Self::OpenDrain;
+
+    /// Like as_output, but using the port's alternate drive settings (see `Ports`).
+    fn as_output_drive_alt(self: Self) -> Self::Output;
+
+    /// Like as_opendrain, but using the port's alternate drive settings (see `Ports`).
+    fn as_opendrain_drive_alt(self: Self) -> Self::OpenDrain;
+
+    /// Like as_opendrain, but with the internal pull-up resistor enabled.
+    fn as_opendrain_pullup(self: Self) -> Self::OpenDrain;
+
+    /// Like as_opendrain, but with the glitch suppression filter enabled on the input.
+    fn as_opendrain_filter(self: Self) -> Self::OpenDrain;
+
+    /// Like as_opendrain, but with both the internal pull-up resistor and the glitch suppression
+    /// filter enabled.
+    fn as_opendrain_pullup_filter(self: Self) -> Self::OpenDrain;
 
     /// Convert the pin into an input pin. The original pin, however configured, is consumed, the
     /// hardware configuration changed to input with no pull-up- or down resistors, and returned as
     /// a pin that implements embedded_hal::digital::InputPin.
     fn as_input(self: Self) -> Self::Input;
+
+    /// Like as_input, but with the internal pull-up resistor enabled.
+    fn as_input_pullup(self: Self) -> Self::InputPullUp;
+
+    /// Like as_input, but with the internal pull-down resistor enabled.
+    fn as_input_pulldown(self: Self) -> Self::InputPullDown;
+
+    /// Like as_input, but with the glitch suppression filter enabled, which removes pulses shorter
+    /// than a few nanoseconds.
+    fn as_input_filter(self: Self) -> Self::InputFilter;
+
+    /// Like as_input_pullup, but with the glitch suppression filter enabled.
+    fn as_input_pullup_filter(self: Self) -> Self::InputPullUpFilter;
+
+    /// Like as_input_pulldown, but with the glitch suppression filter enabled.
+    fn as_input_pulldown_filter(self: Self) -> Self::InputPullDownFilter;
+
+    /// Convert the pin into one that can be passed to an analog peripheral. Its digital input and
+    /// output are disabled, as is the pull-up resistor.
+    ///
+    /// This is only available on EFM32GG, as the analog port (APORT) through which the EFR32's
+    /// analog peripherals reach the pins is not described yet.
+    #[cfg(feature = "chip-efm32gg")]
+    fn as_analog(self: Self) -> Self::Analog;
 }
 
 fn sneak_into_gpio() -> &'static registers::gpio::RegisterBlock {
     unsafe { &*registers::GPIO::ptr() }
 }
 
+/// Level of an EM4 wakeup pin that wakes the device
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Level {
+    Low,
+    High,
+}
+
+/// Pins that can wake the device from EM4
+///
+/// This is implemented for the input configurations of the pins that have an EM4WUn function.
+pub trait EM4WakeupPin {
+    /// The n of the pin's EM4WUn function
+    const EM4WU: u8;
+}
+
+// Position of the EM4WUn bits in the EM4WUEN, polarity and cause registers
+#[cfg(feature = "chip-efm32gg")]
+const EM4WU_OFFSET: u8 = 0;
+#[cfg(feature = "chip-efr32xg1")]
+const EM4WU_OFFSET: u8 = 16;
+
+/// The EM4 wakeup and retention configuration of the GPIO block
+pub struct EM4 {
+    _private: (),
+}
+
+impl EM4 {
+    /// Make the given pin wake up the device from EM4 when it is at the given level.
+    pub fn enable_wakeup<P: EM4WakeupPin>(&mut self, _pin: &P, level: Level) {
+        let gpio = sneak_into_gpio();
+        let bit = EM4WU_OFFSET + P::EM4WU;
+        // unsafe: This struct is the only owner of the EM4 wakeup registers, and bit-banding
+        // leaves the other pins' bits alone.
+        unsafe {
+            #[cfg(feature = "chip-efm32gg")]
+            bitband::change_bit(&gpio.em4wupol, bit, level == Level::High);
+            #[cfg(feature = "chip-efr32xg1")]
+            bitband::change_bit(&gpio.extilevel, bit, level == Level::High);
+            bitband::change_bit(&gpio.em4wuen, bit, true);
+        }
+    }
+
+    /// Stop the given pin from waking up the device from EM4.
+    pub fn disable_wakeup<P: EM4WakeupPin>(&mut self, _pin: &P) {
+        let gpio = sneak_into_gpio();
+        // unsafe: see enable_wakeup
+        unsafe { bitband::change_bit(&gpio.em4wuen, EM4WU_OFFSET + P::EM4WU, false) };
+    }
+
+    /// Tell whether the given pin was a cause of the last wakeup from EM4.
+    ///
+    /// The causes are retained through the reset that follows the wakeup, until they are cleared.
+    pub fn woken_by<P: EM4WakeupPin>(&self, _pin: &P) -> bool {
+        let gpio = sneak_into_gpio();
+        #[cfg(feature = "chip-efm32gg")]
+        let cause = gpio.em4wucause.read().bits();
+        #[cfg(feature = "chip-efr32xg1")]
+        let cause = gpio.if_.read().bits();
+        cause & (1 << (EM4WU_OFFSET + P::EM4WU)) != 0
+    }
+
+    /// Clear the record of which pins caused the last wakeup from EM4.
+    pub fn clear_wakeup_cause(&mut self) {
+        let gpio = sneak_into_gpio();
+        #[cfg(feature = "chip-efm32gg")]
+        gpio.cmd.write(|w| w.em4wuclr().set_bit());
+        #[cfg(feature = "chip-efr32xg1")]
+        gpio.ifc.write(|w| unsafe { w.em4wu().bits(0xffff) });
+    }
+
+    /// Keep the pins' configuration and output levels while in EM4, rather than resetting them
+    /// when EM4 is entered. Retention ends when the device wakes up again.
+    ///
+    /// On EFR32, the setting resides in the EMU, which is why the Emu needs to be passed in.
+    pub fn set_retention(&mut self, emu: &mut emu::Emu, retain: bool) {
+        #[cfg(feature = "chip-efm32gg")]
+        {
+            let _ = emu;
+            sneak_into_gpio().ctrl.write(|w| w.em4ret().bit(retain));
+        }
+        #[cfg(feature = "chip-efr32xg1")]
+        emu.set_em4_io_retention(retain);
+    }
+}
+
+/// Drive current of the alternate drive setting of a port
+///
+/// The regular pin modes always use the Standard drive current.
+#[cfg(feature = "chip-efm32gg")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DriveMode {
+    /// 6 mA drive current
+    Standard,
+    /// 0.1 mA drive current
+    Lowest,
+    /// 20 mA drive current
+    High,
+    /// 2 mA drive current
+    Low,
+}
+
+/// Drive current setting of a port
+#[cfg(feature = "chip-efr32xg1")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DriveStrength {
+    /// 10 mA drive current
+    Strong,
+    /// 1 mA drive current
+    Weak,
+}
+
+macro_rules! ports {
+    ($($PortX: ident: ($x: ident, $px_ctrl: ident),)+) => {
+        $(
+            /// Drive settings shared by all the pins of a port
+            pub struct $PortX {
+                _private: (),
+            }
+
+            impl $PortX {
+                /// Set the drive current used by the pins in the port's alternate drive modes
+                /// (eg. `as_output_drive_alt()`).
+                #[cfg(feature = "chip-efm32gg")]
+                pub fn set_drive_alt(&mut self, mode: DriveMode) {
+                    let gpio = sneak_into_gpio();
+                    gpio.$px_ctrl.modify(|_, w| match mode {
+                        DriveMode::Standard => w.drivemode().standard(),
+                        DriveMode::Lowest => w.drivemode().lowest(),
+                        DriveMode::High => w.drivemode().high(),
+                        DriveMode::Low => w.drivemode().low(),
+                    });
+                }
+
+                /// Set the drive current used by the pins in the port's regular drive modes.
+                #[cfg(feature = "chip-efr32xg1")]
+                pub fn set_drive(&mut self, strength: DriveStrength) {
+                    let gpio = sneak_into_gpio();
+                    gpio.$px_ctrl.modify(|_, w| w.drivestrength().bit(strength == DriveStrength::Weak));
+                }
+
+                /// Set the drive current used by the pins in the port's alternate drive modes
+                /// (eg. `as_output_drive_alt()`).
+                #[cfg(feature = "chip-efr32xg1")]
+                pub fn set_drive_alt(&mut self, strength: DriveStrength) {
+                    let gpio = sneak_into_gpio();
+                    gpio.$px_ctrl.modify(|_, w| w.drivestrengthalt().bit(strength == DriveStrength::Weak));
+                }
+
+                /// Set the slew rate used by the pins in the port's regular drive modes, from 0
+                /// (slowest) to 7 (fastest). The reset value is 5.
+                #[cfg(feature = "chip-efr32xg1")]
+                pub fn set_slew_rate(&mut self, rate: u8) {
+                    assert!(rate <= 7, "Slew rate out of range");
+                    let gpio = sneak_into_gpio();
+                    gpio.$px_ctrl.modify(|_, w| unsafe { w.slewrate().bits(rate) });
+                }
+
+                /// Set the slew rate used by the pins in the port's alternate drive modes, from 0
+                /// (slowest) to 7 (fastest). The reset value is 5.
+                #[cfg(feature = "chip-efr32xg1")]
+                pub fn set_slew_rate_alt(&mut self, rate: u8) {
+                    assert!(rate <= 7, "Slew rate out of range");
+                    let gpio = sneak_into_gpio();
+                    gpio.$px_ctrl.modify(|_, w| unsafe { w.slewratealt().bits(rate) });
+                }
+            }
+        )+
+
+        /// The per-port drive settings, obtained as part of the split Pins
+        pub struct Ports {
+            $(
+                pub $x: $PortX,
+            )+
+        }
+
+        impl Ports {
+            fn new() -> Self {
+                Ports {
+                    $(
+                        $x: $PortX { _private: () },
+                    )+
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "chip-efm32gg")]
+ports!(
+    PortA: (a, pa_ctrl),
+    PortB: (b, pb_ctrl),
+    PortC: (c, pc_ctrl),
+    PortD: (d, pd_ctrl),
+    PortE: (e, pe_ctrl),
+    PortF: (f, pf_ctrl),
+);
+
+#[cfg(feature = "chip-efr32xg1")]
+ports!(
+    PortA: (a, pa_ctrl),
+    PortB: (b, pb_ctrl),
+    PortC: (c, pc_ctrl),
+    PortD: (d, pd_ctrl),
+    PortF: (f, pf_ctrl),
+);
+
+/// The per-pin configuration lock of the GPIO block, obtained as part of the split Pins
+///
+/// Sealing pins clears their bits in the ports' PINLOCKN registers, after which the hardware
+/// ignores changes to their configuration. Other pins, the port settings and the rest of the GPIO
+/// block stay configurable.
+///
+/// A cleared PINLOCKN bit can only be set again by a reset, so sealing is final: the pins stay
+/// locked, and are only accessible through their `Sealed` wrapper, until the device is reset. (The
+/// GPIO-wide LOCKKEY lock, which could be lifted again, is not used, as it would also freeze the
+/// configuration of all pins that were not sealed.)
+pub struct ConfigLock {
+    _private: (),
+}
+
+mod lock {
+    /// Implementation detail of `Lockable`; kept private so that pins are only locked through
+    /// the ConfigLock.
+    pub trait Lock {
+        fn lock(&self);
+    }
+}
+
+/// Pins whose configuration can be locked by `ConfigLock::seal()`
+///
+/// This is implemented for the individual pins, the erased pins of a fixed port, pin groups and
+/// tuples of those.
+pub trait Lockable: lock::Lock {}
+
+impl<T: lock::Lock> Lockable for T {}
+
+macro_rules! lockable_tuple {
+    ($($T:ident: $t:ident),+) => {
+        impl<$($T: lock::Lock),+> lock::Lock for ($($T,)+) {
+            fn lock(&self) {
+                let ($($t,)+) = self;
+                $($t.lock();)+
+            }
+        }
+    }
+}
+
+lockable_tuple!(A: a, B: b);
+lockable_tuple!(A: a, B: b, C: c);
+lockable_tuple!(A: a, B: b, C: c, D: d);
+lockable_tuple!(A: a, B: b, C: c, D: d, E: e);
+lockable_tuple!(A: a, B: b, C: c, D: d, E: e, F: f);
+
+impl ConfigLock {
+    /// Lock the configuration of the given pins until the next reset, and hand them out only for
+    /// use as locked pins.
+    pub fn seal<P: Lockable>(&mut self, pins: P) -> Sealed<P> {
+        pins.lock();
+        Sealed { pins }
+    }
+}
+
+/// Pins that can be read but not reconfigured, obtained by `ConfigLock::seal()`
+///
+/// The pins are accessible through dereferencing, which allows reading them but not moving them
+/// out to change their mode. There is no way back to the unsealed pins, as the hardware keeps
+/// them locked until the next reset.
+pub struct Sealed<P: Lockable> {
+    pins: P,
+}
+
+impl<P: Lockable> core::ops::Deref for Sealed<P> {
+    type Target = P;
+
+    fn deref(&self) -> &P {
+        &self.pins
+    }
+}
+
+/// The pins that are routed to the debug interface after reset, obtained as part of the split Pins
+///
+/// As long as they are not released with `disable_debug_pins()`, a debugger can be connected to
+/// the device. (SWO is not enabled at reset, so its pins are handed out with the regular pins.)
+pub struct DebugPins {
+    _private: (),
+}
+
+/// The pins freed by `DebugPins::disable_debug_pins()`
+pub struct FreedDebugPins {
+    /// SWCLK (on EFR32 also TCK)
+    pub pf0: pins::PF0<Disabled>,
+    /// SWDIO (on EFR32 also TMS)
+    pub pf1: pins::PF1<Disabled>,
+    /// TDO
+    #[cfg(feature = "chip-efr32xg1")]
+    pub pf2: pins::PF2<Disabled>,
+    /// TDI
+    #[cfg(feature = "chip-efr32xg1")]
+    pub pf3: pins::PF3<Disabled>,
+}
+
+impl DebugPins {
+    /// Disconnect the debug interface from its pins, and hand them out for general use.
+    ///
+    /// From then on, a debugger can only be connected again after a reset (which might need to
+    /// happen under the debugger's control, before the firmware disables the pins again).
+    pub fn disable_debug_pins(self) -> FreedDebugPins {
+        let gpio = sneak_into_gpio();
+        #[cfg(feature = "chip-efm32gg")]
+        gpio.route.modify(|_, w| w.swclkpen().bit(false).swdiopen().bit(false));
+        #[cfg(feature = "chip-efr32xg1")]
+        gpio.routepen.modify(|_, w| w
+            .swclktckpen().bit(false)
+            .swdiotmspen().bit(false)
+            .tdopen().bit(false)
+            .tdipen().bit(false)
+            );
+
+        FreedDebugPins {
+            pf0: pins::PF0 { _mode: PhantomData },
+            pf1: pins::PF1 { _mode: PhantomData },
+            #[cfg(feature = "chip-efr32xg1")]
+            pf2: pins::PF2 { _mode: PhantomData },
+            #[cfg(feature = "chip-efr32xg1")]
+            pf3: pins::PF3 { _mode: PhantomData },
+        }
+    }
+}
+
+/// Signal edge on which an external interrupt is triggered
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Edge {
+    Rising,
+    Falling,
+    Both,
+}
+
+/// A pin that is configured to trigger an external interrupt, obtained by
+/// `ExtiPin::with_interrupt()` on a pin in an input mode and its interrupt line.
+///
+/// Interrupts on even lines are served by the GPIO_EVEN interrupt handler, those on odd lines by
+/// GPIO_ODD; `interrupt()` tells which one it is. The handler should check `is_pending()` on the
+/// pins it is responsible for and clear the flags, as the interrupt fires again otherwise.
+pub struct InterruptPin<Pin, Line> {
+    pin: Pin,
+    line: Line,
+}
+
+/// Pins that can be combined with their external interrupt line
+pub trait ExtiPin: Sized {
+    type Line;
+
+    /// Configure the pin's external interrupt line to be triggered by this pin on the given
+    /// edge(s).
+    ///
+    /// The interrupt is not enabled yet; see `InterruptPin::enable_interrupt()`.
+    fn with_interrupt(self, line: Self::Line, edge: Edge) -> InterruptPin<Self, Self::Line>;
+}
+
+/// The external interrupt lines, identified by their number
+pub trait ExtiLine {
+    const LINE: u8;
+}
+
+impl<P, L> InterruptPin<P, L> where
+    L: ExtiLine,
+{
+    /// Enable the interrupt, so that a trigger sets the interrupt flag and requests the GPIO_EVEN
+    /// or GPIO_ODD interrupt.
+    pub fn enable_interrupt(&mut self) {
+        let gpio = sneak_into_gpio();
+        // unsafe: We own the line and thus its bits in the register, and bit-band access leaves
+        // the others alone.
+        unsafe { bitband::change_bit(&gpio.ien, L::LINE, true) };
+    }
+
+    /// Disable the interrupt. Triggers still set the interrupt flag.
+    pub fn disable_interrupt(&mut self) {
+        let gpio = sneak_into_gpio();
+        // unsafe: see enable_interrupt
+        unsafe { bitband::change_bit(&gpio.ien, L::LINE, false) };
+    }
+
+    /// Tell whether the interrupt flag of the line is set.
+    pub fn is_pending(&self) -> bool {
+        let gpio = sneak_into_gpio();
+        gpio.if_.read().bits() & (1 << L::LINE) != 0
+    }
+
+    /// Clear the interrupt flag of the line.
+    pub fn clear_pending(&mut self) {
+        let gpio = sneak_into_gpio();
+        // unsafe: Writing a one to IFC only clears our own flag.
+        gpio.ifc.write(|w| unsafe { w.bits(1 << L::LINE) });
+    }
+
+    /// The interrupt that serves this pin's line.
+    pub fn interrupt(&self) -> registers::Interrupt {
+        if L::LINE % 2 == 0 {
+            registers::Interrupt::GPIO_EVEN
+        } else {
+            registers::Interrupt::GPIO_ODD
+        }
+    }
+
+    /// Disable the interrupt and its triggers, and give back the pin and the line.
+    pub fn free(mut self) -> (P, L) {
+        self.disable_interrupt();
+        set_edge(L::LINE, None);
+        self.clear_pending();
+        (self.pin, self.line)
+    }
+}
+
+fn set_edge(line: u8, edge: Option<Edge>) {
+    let gpio = sneak_into_gpio();
+    let rising = edge == Some(Edge::Rising) || edge == Some(Edge::Both);
+    let falling = edge == Some(Edge::Falling) || edge == Some(Edge::Both);
+    // unsafe: see enable_interrupt
+    unsafe {
+        bitband::change_bit(&gpio.extirise, line, rising);
+        bitband::change_bit(&gpio.extifall, line, falling);
+    }
+}
+
+/// Select the port whose pin drives the given interrupt line.
+fn select_port(line: u8, port: u8) {
+    let gpio = sneak_into_gpio();
+    let shift = 4 * (line % 8);
+    // Unlike the trigger and enable bits, the port fields span several bits and can not be
+    // bit-banded.
+    cortex_m::interrupt::free(|_| {
+        // unsafe: Only the line's own field is altered; all values from 0 to 5 are valid ports.
+        if line < 8 {
+            gpio.extipsell.modify(|r, w| unsafe { w.bits(r.bits() & !(0xf << shift) | (port as u32) << shift) });
+        } else {
+            gpio.extipselh.modify(|r, w| unsafe { w.bits(r.bits() & !(0xf << shift) | (port as u32) << shift) });
+        }
+        // On this chip, a line can be driven by any pin in its group of four; using the pin of
+        // the same number keeps the rules identical to the older chips.
+        #[cfg(feature = "chip-efr32xg1")]
+        {
+            let pin = (line % 4) as u32;
+            if line < 8 {
+                gpio.extipinsell.modify(|r, w| unsafe { w.bits(r.bits() & !(0xf << shift) | pin << shift) });
+            } else {
+                gpio.extipinselh.modify(|r, w| unsafe { w.bits(r.bits() & !(0xf << shift) | pin << shift) });
+            }
+        }
+    });
+}
+
+macro_rules! exti {
+    ($($ExtiLineI: ident: ($linei: ident, $i: expr, [$($PXi: ident: $port: expr),+]),)+) => {
+        $(
+            /// External interrupt line, which can be used with any pin of the same number
+            pub struct $ExtiLineI {
+                _private: (),
+            }
+
+            impl ExtiLine for $ExtiLineI {
+                const LINE: u8 = $i;
+            }
+
+            $(
+                impl<Mode: InputMode> ExtiPin for pins::$PXi<Mode> {
+                    type Line = $ExtiLineI;
+
+                    fn with_interrupt(self, line: $ExtiLineI, edge: Edge) -> InterruptPin<Self, $ExtiLineI> {
+                        select_port($i, $port);
+                        set_edge($i, Some(edge));
+                        InterruptPin { pin: self, line }
+                    }
+                }
+            )+
+        )+
+
+        /// The external interrupt lines, obtained as part of the split Pins
+        pub struct ExtiLines {
+            $(
+                pub $linei: $ExtiLineI,
+            )+
+        }
+
+        impl ExtiLines {
+            fn new() -> Self {
+                ExtiLines {
+                    $(
+                        $linei: $ExtiLineI { _private: () },
+                    )+
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "chip-efm32gg")]
+exti!(
+    ExtiLine0: (line0, 0, [PA0: 0, PB0: 1, PC0: 2, PD0: 3, PE0: 4, PF0: 5]),
+    ExtiLine1: (line1, 1, [PA1: 0, PB1: 1, PC1: 2, PD1: 3, PE1: 4, PF1: 5]),
+    ExtiLine2: (line2, 2, [PA2: 0, PB2: 1, PC2: 2, PD2: 3, PE2: 4, PF2: 5]),
+    ExtiLine3: (line3, 3, [PA3: 0, PB3: 1, PC3: 2, PD3: 3, PE3: 4, PF3: 5]),
+    ExtiLine4: (line4, 4, [PA4: 0, PB4: 1, PC4: 2, PD4: 3, PE4: 4, PF4: 5]),
+    ExtiLine5: (line5, 5, [PA5: 0, PB5: 1, PC5: 2, PD5: 3, PE5: 4, PF5: 5]),
+    ExtiLine6: (line6, 6, [PA6: 0, PB6: 1, PC6: 2, PD6: 3, PE6: 4, PF6: 5]),
+    ExtiLine7: (line7, 7, [PA7: 0, PB7: 1, PC7: 2, PD7: 3, PE7: 4, PF7: 5]),
+    ExtiLine8: (line8, 8, [PA8: 0, PB8: 1, PC8: 2, PD8: 3, PE8: 4, PF8: 5]),
+    ExtiLine9: (line9, 9, [PA9: 0, PB9: 1, PC9: 2, PD9: 3, PE9: 4, PF9: 5]),
+    ExtiLine10: (line10, 10, [PA10: 0, PB10: 1, PC10: 2, PD10: 3, PE10: 4, PF10: 5]),
+    ExtiLine11: (line11, 11, [PA11: 0, PB11: 1, PC11: 2, PD11: 3, PE11: 4, PF11: 5]),
+    ExtiLine12: (line12, 12, [PA12: 0, PB12: 1, PC12: 2, PD12: 3, PE12: 4, PF12: 5]),
+    ExtiLine13: (line13, 13, [PA13: 0, PB13: 1, PC13: 2, PD13: 3, PE13: 4]),
+    ExtiLine14: (line14, 14, [PA14: 0, PB14: 1, PC14: 2, PD14: 3, PE14: 4]),
+    ExtiLine15: (line15, 15, [PA15: 0, PB15: 1, PC15: 2, PD15: 3, PE15: 4]),
+);
+
+#[cfg(feature = "chip-efr32xg1")]
+exti!(
+    ExtiLine0: (line0, 0, [PA0: 0, PF0: 5]),
+    ExtiLine1: (line1, 1, [PA1: 0, PF1: 5]),
+    ExtiLine2: (line2, 2, [PA2: 0, PF2: 5]),
+    ExtiLine3: (line3, 3, [PA3: 0, PF3: 5]),
+    ExtiLine4: (line4, 4, [PA4: 0, PF4: 5]),
+    ExtiLine5: (line5, 5, [PA5: 0, PF5: 5]),
+    ExtiLine6: (line6, 6, [PC6: 2, PF6: 5]),
+    ExtiLine7: (line7, 7, [PC7: 2, PF7: 5]),
+    ExtiLine8: (line8, 8, [PC8: 2]),
+    ExtiLine9: (line9, 9, [PC9: 2, PD9: 3]),
+    ExtiLine10: (line10, 10, [PC10: 2, PD10: 3]),
+    ExtiLine11: (line11, 11, [PB11: 1, PC11: 2, PD11: 3]),
+    ExtiLine12: (line12, 12, [PB12: 1, PD12: 3]),
+    ExtiLine13: (line13, 13, [PB13: 1, PD13: 3]),
+    ExtiLine14: (line14, 14, [PB14: 1, PD14: 3]),
+    ExtiLine15: (line15, 15, [PB15: 1, PD15: 3]),
+);
+
+macro_rules! em4wu {
+    ($($PXi: ident: $n: expr,)+) => {
+        $(
+            impl<Mode: InputMode> EM4WakeupPin for pins::$PXi<Mode> {
+                const EM4WU: u8 = $n;
+            }
+        )+
+    }
+}
+
+#[cfg(feature = "chip-efm32gg")]
+em4wu!(
+    PA0: 0,
+    PA6: 1,
+    PC9: 2,
+    PF1: 3,
+    PF2: 4,
+    PE13: 5,
+);
+
+#[cfg(feature = "chip-efr32xg1")]
+em4wu!(
+    PF2: 0,
+    PF7: 1,
+    PD14: 4,
+    PA3: 8,
+    PB13: 9,
+    PC10: 12,
+);
+
+#[cfg(feature = "chip-efm32gg")]
+pub mod analog {
+    //! Analog functions of pins
+    //!
+    //! The traits in here are implemented for the pins that are configured with `.as_analog()`
+    //! and are connected to the respective analog peripheral, so that drivers for that peripheral
+    //! can take ownership of the pins they sample or drive.
+    //!
+    //! This is only available on EFM32GG. On EFR32, the analog peripherals reach the pins through
+    //! the analog port (APORT) instead of fixed channels, whose mapping is not described yet.
+
+    use super::{pins, Analog};
+
+    pub(super) mod sealed {
+        pub trait Sealed {}
+    }
+
+    /// Pins that can be sampled by an ADC
+    pub trait AdcChannel<ADC>: sealed::Sealed {
+        /// The channel number in the ADC's input selection
+        const CHANNEL: u8;
+    }
+
+    /// Pins that can be inputs to an analog comparator
+    pub trait AcmpInput<ACMP>: sealed::Sealed {
+        /// The channel number in the comparator's input selection
+        const CHANNEL: u8;
+    }
+
+    /// Pins that can be driven by a DAC
+    pub trait DacOutput<DAC>: sealed::Sealed {
+        /// The DAC channel that drives the pin
+        const CHANNEL: u8;
+    }
+
+    macro_rules! analog {
+        ($Trait:ident<$Peripheral:ident>: $($PXi:ident: $channel:expr,)+) => {
+            $(
+                impl $Trait<registers::$Peripheral> for pins::$PXi<Analog> {
+                    const CHANNEL: u8 = $channel;
+                }
+            )+
+        }
+    }
+
+    analog!(AdcChannel<ADC0>:
+        PD0: 0,
+        PD1: 1,
+        PD2: 2,
+        PD3: 3,
+        PD4: 4,
+        PD5: 5,
+        PD6: 6,
+        PD7: 7,
+    );
+
+    analog!(AcmpInput<ACMP0>:
+        PC0: 0,
+        PC1: 1,
+        PC2: 2,
+        PC3: 3,
+        PC4: 4,
+        PC5: 5,
+        PC6: 6,
+        PC7: 7,
+    );
+
+    analog!(AcmpInput<ACMP1>:
+        PC8: 0,
+        PC9: 1,
+        PC10: 2,
+        PC11: 3,
+        PC12: 4,
+        PC13: 5,
+        PC14: 6,
+        PC15: 7,
+    );
+
+    analog!(DacOutput<DAC0>:
+        PB11: 0,
+        PB12: 1,
+    );
+}
+
+#[cfg(feature = "chip-efm32gg")]
+const PORT_STRIDE: usize = 0x24;
+#[cfg(feature = "chip-efr32xg1")]
+const PORT_STRIDE: usize = 0x30;
+
+/// A pin whose port and number are only known at runtime, obtained by `.downgrade()`
+///
+/// This allows keeping pins of different ports in an array, at the cost of computing the register
+/// addresses at runtime.
+pub struct Pin<Mode> {
+    port: u8,
+    index: u8,
+    _mode: PhantomData<Mode>,
+}
+
+impl<Mode> Pin<Mode> {
+    /// The pin's port, counting from 0 for port A
+    pub fn port(&self) -> u8 {
+        self.port
+    }
+
+    /// The pin's number inside its port
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Address of the given register of this pin's port, given the port A register
+    fn register<T>(&self, port_a_register: &T) -> *const u32 {
+        (port_a_register as *const T as usize + self.port as usize * PORT_STRIDE) as *const u32
+    }
+}
+
+impl<Mode: OutputMode> digital::OutputPin for Pin<Mode> {
+    fn set_low(self: &mut Self) {
+        let gpio = sneak_into_gpio();
+        // unsafe: see pins::PA0::set_low
+        unsafe { bitband::change_bit(self.register(&gpio.pa_dout), self.index, false); }
+    }
+
+    fn set_high(self: &mut Self) {
+        let gpio = sneak_into_gpio();
+        // unsafe: see pins::PA0::set_low
+        unsafe { bitband::change_bit(self.register(&gpio.pa_dout), self.index, true); }
+    }
+}
+
+#[cfg(feature = "unproven")]
+impl<Mode: OutputMode> digital::StatefulOutputPin for Pin<Mode> {
+    fn is_set_low(self: &Self) -> bool {
+        let gpio = sneak_into_gpio();
+        // unsafe: The register exists, and reading it has no side effects.
+        let dout = unsafe { read_volatile(self.register(&gpio.pa_dout)) };
+        dout & (1 << self.index) == 0
+    }
+
+    fn is_set_high(self: &Self) -> bool {
+        !self.is_set_low()
+    }
+}
+
+#[cfg(feature = "unproven")]
+impl<Mode: OutputMode> digital::ToggleableOutputPin for Pin<Mode> {
+    fn toggle(self: &mut Self) {
+        let gpio = sneak_into_gpio();
+        // unsafe: see pins::PA0::toggle
+        unsafe { write_volatile(self.register(&gpio.pa_douttgl) as *mut u32, 1 << self.index); }
+    }
+}
+
+#[cfg(feature = "unproven")]
+impl<Mode: InputMode> digital::InputPin for Pin<Mode> {
+    fn is_low(self: &Self) -> bool {
+        let gpio = sneak_into_gpio();
+        // unsafe: see is_set_low
+        let din = unsafe { read_volatile(self.register(&gpio.pa_din)) };
+        din & (1 << self.index) == 0
+    }
+
+    fn is_high(self: &Self) -> bool {
+        !self.is_low()
+    }
+}
+
+mod group {
+    /// Implementation detail of the pin groups: Pins of the port `Port` (given as its erased pin
+    /// type) that can be added to its group
+    pub trait Member<Port> {
+        fn index(&self) -> u8;
+    }
+
+    /// Implementation detail of the pin groups: Tuples of pins that another pin can be appended to
+    pub trait Append<P> {
+        type Output;
+
+        fn append(self, pin: P) -> Self::Output;
+    }
+}
+
+macro_rules! append_tuple {
+    () => {};
+    ($Head: ident $($T: ident)*) => {
+        append_tuple!($($T)*);
+
+        #[allow(non_snake_case)]
+        impl<$($T,)* P> group::Append<P> for ($($T,)*) {
+            type Output = ($($T,)* P,);
+
+            fn append(self, pin: P) -> Self::Output {
+                let ($($T,)*) = self;
+                ($($T,)* pin,)
+            }
+        }
+    }
+}
+
+// A port has 16 pins, so groups hold tuples of up to 16 pins.
+append_tuple!(A B C D E F G H I J K L M N O Q R);
+
+macro_rules! port_pin {
+    ($PX: ident, $PXGroup: ident, $port: expr, $px_din: ident, $px_dout: ident, $px_douttgl: ident, $px_doutset: ident, $px_doutclr: ident, $px_pinlockn: ident) => {
+        /// A pin of a fixed port whose number is only known at runtime, obtained by
+        /// `.erase_number()`
+        pub struct $PX<Mode> {
+            index: u8,
+            _mode: PhantomData<Mode>,
+        }
+
+        impl<Mode> $PX<Mode> {
+            /// The pin's number inside its port
+            pub fn index(&self) -> u8 {
+                self.index
+            }
+
+            /// Turn the pin into one whose port is only known at runtime as well.
+            pub fn downgrade(self) -> Pin<Mode> {
+                Pin { port: $port, index: self.index, _mode: PhantomData }
+            }
+
+            /// Lock the configuration of the port's pins whose bits are set in `mask`.
+            fn lock_mask(mask: u16) {
+                let gpio = sneak_into_gpio();
+                for i in (0..16).filter(|i| mask & (1 << i) != 0) {
+                    // unsafe: The ConfigLock that all locking goes through owns PINLOCKN, and
+                    // bit-banding leaves the other pins' bits alone.
+                    unsafe { bitband::change_bit(&gpio.$px_pinlockn, i, false) };
+                }
+            }
+        }
+
+        impl<Mode> lock::Lock for $PX<Mode> {
+            fn lock(&self) {
+                Self::lock_mask(1 << self.index);
+            }
+        }
+
+        impl<Mode> group::Member<$PX<Mode>> for $PX<Mode> {
+            fn index(&self) -> u8 {
+                self.index
+            }
+        }
+
+        impl<Mode: OutputMode> digital::OutputPin for $PX<Mode> {
+            fn set_low(self: &mut Self) {
+                let gpio = sneak_into_gpio();
+                // unsafe: see pins::PA0::set_low
+                unsafe { bitband::change_bit(&gpio.$px_dout, self.index, false); }
+            }
+
+            fn set_high(self: &mut Self) {
+                let gpio = sneak_into_gpio();
+                unsafe { bitband::change_bit(&gpio.$px_dout, self.index, true); }
+            }
+        }
+
+        #[cfg(feature = "unproven")]
+        impl<Mode: OutputMode> digital::StatefulOutputPin for $PX<Mode> {
+            fn is_set_low(self: &Self) -> bool {
+                let gpio = sneak_into_gpio();
+                gpio.$px_dout.read().bits() & (1 << self.index) == 0
+            }
+
+            fn is_set_high(self: &Self) -> bool {
+                !self.is_set_low()
+            }
+        }
+
+        #[cfg(feature = "unproven")]
+        impl<Mode: OutputMode> digital::ToggleableOutputPin for $PX<Mode> {
+            fn toggle(self: &mut Self) {
+                let gpio = sneak_into_gpio();
+                // unsafe: see pins::PA0::toggle
+                gpio.$px_douttgl.write(|w| unsafe { w.bits(1 << self.index) });
+            }
+        }
+
+        #[cfg(feature = "unproven")]
+        impl<Mode: InputMode> digital::InputPin for $PX<Mode> {
+            fn is_low(self: &Self) -> bool {
+                let gpio = sneak_into_gpio();
+                gpio.$px_din.read().bits() & (1 << self.index) == 0
+            }
+
+            fn is_high(self: &Self) -> bool {
+                !self.is_low()
+            }
+        }
+
+        /// Several pins of the same port that are written or read together
+        ///
+        /// The group is built from the individual pins, and values passed in or out of it have
+        /// their bits at the pins' positions in the port. Bits of pins that are not part of the
+        /// group are ignored on writing and read as zero.
+        ///
+        /// The pins are kept in the group as a tuple `Pins` in the order they were added, and
+        /// are given back as such by `.free()`.
+        pub struct $PXGroup<Mode, Pins = ()> {
+            mask: u16,
+            pins: Pins,
+            _mode: PhantomData<Mode>,
+        }
+
+        impl<Mode> Default for $PXGroup<Mode> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<Mode> $PXGroup<Mode> {
+            /// Create a group that does not contain any pins yet
+            pub fn new() -> Self {
+                $PXGroup { mask: 0, pins: (), _mode: PhantomData }
+            }
+        }
+
+        impl<Mode, Pins> $PXGroup<Mode, Pins> {
+            /// Add a pin (a numbered one like `PA0`, or an erased `PA`) to the group
+            pub fn with<P>(self, pin: P) -> $PXGroup<Mode, Pins::Output> where
+                P: group::Member<$PX<Mode>>,
+                Pins: group::Append<P>,
+            {
+                let mask = self.mask | (1 << pin.index());
+                $PXGroup { mask, pins: self.pins.append(pin), _mode: PhantomData }
+            }
+
+            /// The bits of the port that belong to the group
+            pub fn mask(&self) -> u16 {
+                self.mask
+            }
+
+            /// Dissolve the group into the pins it was built from.
+            pub fn free(self) -> Pins {
+                self.pins
+            }
+        }
+
+        impl<Mode: OutputMode, Pins> $PXGroup<Mode, Pins> {
+            /// Set all pins of the group to the corresponding bits of `value` at once.
+            pub fn write(&mut self, value: u16) {
+                let gpio = sneak_into_gpio();
+                // Flipping the differing bits through DOUTTGL changes all of them in a single
+                // write, and leaves the port's other pins alone even if they are changed between
+                // reading and writing.
+                let toggle = (gpio.$px_dout.read().bits() as u16 ^ value) & self.mask;
+                // unsafe: We own the pins of all bits that are written
+                gpio.$px_douttgl.write(|w| unsafe { w.bits(toggle as u32) });
+            }
+
+            /// Set the pins of the group whose bits are set in `bits` high at once.
+            pub fn set_high(&mut self, bits: u16) {
+                let gpio = sneak_into_gpio();
+                #[cfg(feature = "chip-efm32gg")]
+                // unsafe: see write
+                gpio.$px_doutset.write(|w| unsafe { w.bits((bits & self.mask) as u32) });
+                #[cfg(feature = "chip-efr32xg1")]
+                {
+                    // There is no DOUTSET; see write
+                    let toggle = !gpio.$px_dout.read().bits() as u16 & bits & self.mask;
+                    gpio.$px_douttgl.write(|w| unsafe { w.bits(toggle as u32) });
+                }
+            }
+
+            /// Set the pins of the group whose bits are set in `bits` low at once.
+            pub fn set_low(&mut self, bits: u16) {
+                let gpio = sneak_into_gpio();
+                #[cfg(feature = "chip-efm32gg")]
+                // unsafe: see write
+                gpio.$px_doutclr.write(|w| unsafe { w.bits((bits & self.mask) as u32) });
+                #[cfg(feature = "chip-efr32xg1")]
+                {
+                    // There is no DOUTCLR; see write
+                    let toggle = gpio.$px_dout.read().bits() as u16 & bits & self.mask;
+                    gpio.$px_douttgl.write(|w| unsafe { w.bits(toggle as u32) });
+                }
+            }
+
+            /// Toggle the pins of the group whose bits are set in `bits` at once.
+            pub fn toggle(&mut self, bits: u16) {
+                let gpio = sneak_into_gpio();
+                // unsafe: see write
+                gpio.$px_douttgl.write(|w| unsafe { w.bits((bits & self.mask) as u32) });
+            }
+
+            /// The values the group's pins are currently set to
+            pub fn read_output(&self) -> u16 {
+                let gpio = sneak_into_gpio();
+                gpio.$px_dout.read().bits() as u16 & self.mask
+            }
+        }
+
+        impl<Mode: InputMode, Pins> $PXGroup<Mode, Pins> {
+            /// Read the input values of all pins of the group at once.
+            pub fn read(&self) -> u16 {
+                let gpio = sneak_into_gpio();
+                gpio.$px_din.read().bits() as u16 & self.mask
+            }
+        }
+
+        impl<Mode, Pins> lock::Lock for $PXGroup<Mode, Pins> {
+            fn lock(&self) {
+                $PX::<Mode>::lock_mask(self.mask);
+            }
+        }
+    }
+}
+
+port_pin!(PA, PAGroup, 0, pa_din, pa_dout, pa_douttgl, pa_doutset, pa_doutclr, pa_pinlockn);
+port_pin!(PB, PBGroup, 1, pb_din, pb_dout, pb_douttgl, pb_doutset, pb_doutclr, pb_pinlockn);
+port_pin!(PC, PCGroup, 2, pc_din, pc_dout, pc_douttgl, pc_doutset, pc_doutclr, pc_pinlockn);
+port_pin!(PD, PDGroup, 3, pd_din, pd_dout, pd_douttgl, pd_doutset, pd_doutclr, pd_pinlockn);
+#[cfg(feature = "chip-efm32gg")]
+port_pin!(PE, PEGroup, 4, pe_din, pe_dout, pe_douttgl, pe_doutset, pe_doutclr, pe_pinlockn);
+port_pin!(PF, PFGroup, 5, pf_din, pf_dout, pf_douttgl, pf_doutset, pf_doutclr, pf_pinlockn);
+
 macro_rules! gpio {
-    ([$($PXi:ident: ($pxi:ident, $i:expr, $px_din:ident, $px_dout:ident, $modei:ident, $px_modehl:ident),)+]) => {
+    ([$($PXi:ident: ($pxi:ident, $i:expr, $px_din:ident, $px_dout:ident, $px_douttgl:ident, $modei:ident, $px_modehl:ident, $PX:ident, $port:expr),)+]) => {
 
         pub mod pins {
             use embedded_hal::digital;
@@ -79,7 +1154,7 @@ macro_rules! gpio {
                     pub(super) _mode: PhantomData<Mode>,
                 }
 
-                impl digital::OutputPin for $PXi<Output> {
+                impl<Mode: OutputMode> digital::OutputPin for $PXi<Mode> {
                     fn set_low(self: &mut Self) {
                         // This implementation uses bit-banding on all EFx32 devices. EFM2 would
                         // have explicit set/clear registers, but bit-banding is available there
@@ -99,7 +1174,7 @@ macro_rules! gpio {
                     }
                 }
                 #[cfg(feature = "unproven")]
-                impl digital::StatefulOutputPin for $PXi<Output> {
+                impl<Mode: OutputMode> digital::StatefulOutputPin for $PXi<Mode> {
                     fn is_set_low(self: &Self) -> bool {
                         let gpio = sneak_into_gpio();
                         gpio.$px_dout.read().bits() & (1 << $i) == 0
@@ -110,7 +1185,16 @@ macro_rules! gpio {
                     }
                 }
                 #[cfg(feature = "unproven")]
-                impl digital::InputPin for $PXi<Input> {
+                impl<Mode: OutputMode> digital::ToggleableOutputPin for $PXi<Mode> {
+                    fn toggle(self: &mut Self) {
+                        let gpio = sneak_into_gpio();
+                        // unsafe: We own that pin, and writing a single bit to DOUTTGL leaves the
+                        // others alone.
+                        gpio.$px_douttgl.write(|w| unsafe { w.bits(1 << $i) });
+                    }
+                }
+                #[cfg(feature = "unproven")]
+                impl<Mode: InputMode> digital::InputPin for $PXi<Mode> {
                     fn is_low(self: &Self) -> bool {
                         let gpio = sneak_into_gpio();
                         gpio.$px_din.read().bits() & (1 << $i) == 0
@@ -121,10 +1205,59 @@ macro_rules! gpio {
                     }
                 }
 
+                impl<Mode> $PXi<Mode> {
+                    /// Turn the pin into one whose number in the port is only known at runtime.
+                    pub fn erase_number(self) -> $PX<Mode> {
+                        $PX { index: $i, _mode: PhantomData }
+                    }
+
+                    /// Turn the pin into one whose port and number are only known at runtime.
+                    pub fn downgrade(self) -> Pin<Mode> {
+                        Pin { port: $port, index: $i, _mode: PhantomData }
+                    }
+
+                    /// Set the pin's mode, after setting its DOUT bit, which selects the pull
+                    /// direction or the filter in some of the input and open drain modes.
+                    fn configure<NewMode>(self, mode: impl FnOnce(&mut registers::gpio::$px_modehl::W) -> &mut registers::gpio::$px_modehl::W, dout: bool) -> $PXi<NewMode> {
+                        let gpio = sneak_into_gpio();
+                        // unsafe: see set_low
+                        unsafe { bitband::change_bit(&gpio.$px_dout, $i, dout); }
+                        gpio.$px_modehl.modify(|_, w| mode(w));
+
+                        $PXi { _mode: PhantomData }
+                    }
+                }
+
+                impl<Mode> From<$PXi<Mode>> for $PX<Mode> {
+                    fn from(pin: $PXi<Mode>) -> Self {
+                        pin.erase_number()
+                    }
+                }
+
+                impl<Mode> group::Member<$PX<Mode>> for $PXi<Mode> {
+                    fn index(&self) -> u8 {
+                        $i
+                    }
+                }
+
+                impl<Mode> lock::Lock for $PXi<Mode> {
+                    fn lock(&self) {
+                        $PX::<Mode>::lock_mask(1 << $i);
+                    }
+                }
+
                 impl<Mode> EFM32Pin for $PXi<Mode> {
                     type Disabled = $PXi<Disabled>;
                     type Output = $PXi<Output>;
+                    type OpenDrain = $PXi<OpenDrain>;
                     type Input = $PXi<Input>;
+                    type InputPullUp = $PXi<InputPullUp>;
+                    type InputPullDown = $PXi<InputPullDown>;
+                    type InputFilter = $PXi<InputFilter>;
+                    type InputPullUpFilter = $PXi<InputPullUpFilter>;
+                    type InputPullDownFilter = $PXi<InputPullDownFilter>;
+                    #[cfg(feature = "chip-efm32gg")]
+                    type Analog = $PXi<Analog>;
 
                     fn as_output(self: Self) -> Self::Output {
                         let gpio = sneak_into_gpio();
@@ -132,26 +1265,228 @@ macro_rules! gpio {
 
                         $PXi { _mode: PhantomData }
                     }
-                    fn as_opendrain(self: Self) -> Self::Output {
+                    fn as_opendrain(self: Self) -> Self::OpenDrain {
                         let gpio = sneak_into_gpio();
                         gpio.$px_modehl.modify(|_, w| w.$modei().wiredand());
 
                         $PXi { _mode: PhantomData }
                     }
-                    fn as_input(self: Self) -> Self::Input {
+                    fn as_output_drive_alt(self: Self) -> Self::Output {
+                        let gpio = sneak_into_gpio();
+                        #[cfg(feature = "chip-efm32gg")]
+                        gpio.$px_modehl.modify(|_, w| w.$modei().pushpulldrive());
+                        #[cfg(feature = "chip-efr32xg1")]
+                        gpio.$px_modehl.modify(|_, w| w.$modei().pushpullalt());
+
+                        $PXi { _mode: PhantomData }
+                    }
+                    fn as_opendrain_drive_alt(self: Self) -> Self::OpenDrain {
+                        let gpio = sneak_into_gpio();
+                        #[cfg(feature = "chip-efm32gg")]
+                        gpio.$px_modehl.modify(|_, w| w.$modei().wiredanddrive());
+                        #[cfg(feature = "chip-efr32xg1")]
+                        gpio.$px_modehl.modify(|_, w| w.$modei().wiredandalt());
+
+                        $PXi { _mode: PhantomData }
+                    }
+                    fn as_opendrain_pullup(self: Self) -> Self::OpenDrain {
+                        let gpio = sneak_into_gpio();
+                        gpio.$px_modehl.modify(|_, w| w.$modei().wiredandpullup());
+
+                        $PXi { _mode: PhantomData }
+                    }
+                    fn as_opendrain_filter(self: Self) -> Self::OpenDrain {
+                        let gpio = sneak_into_gpio();
+                        gpio.$px_modehl.modify(|_, w| w.$modei().wiredandfilter());
+
+                        $PXi { _mode: PhantomData }
+                    }
+                    fn as_opendrain_pullup_filter(self: Self) -> Self::OpenDrain {
                         let gpio = sneak_into_gpio();
-                        gpio.$px_modehl.modify(|_, w| w.$modei().input());
+                        gpio.$px_modehl.modify(|_, w| w.$modei().wiredandpullupfilter());
 
                         $PXi { _mode: PhantomData }
                     }
+                    fn as_input(self: Self) -> Self::Input {
+                        // In INPUT mode, DOUT enables the filter
+                        self.configure(|w| w.$modei().input(), false)
+                    }
+                    fn as_input_pullup(self: Self) -> Self::InputPullUp {
+                        // In INPUTPULL(FILTER) mode, DOUT selects the pull direction
+                        self.configure(|w| w.$modei().inputpull(), true)
+                    }
+                    fn as_input_pulldown(self: Self) -> Self::InputPullDown {
+                        self.configure(|w| w.$modei().inputpull(), false)
+                    }
+                    fn as_input_filter(self: Self) -> Self::InputFilter {
+                        self.configure(|w| w.$modei().input(), true)
+                    }
+                    fn as_input_pullup_filter(self: Self) -> Self::InputPullUpFilter {
+                        self.configure(|w| w.$modei().inputpullfilter(), true)
+                    }
+                    fn as_input_pulldown_filter(self: Self) -> Self::InputPullDownFilter {
+                        self.configure(|w| w.$modei().inputpullfilter(), false)
+                    }
+                    #[cfg(feature = "chip-efm32gg")]
+                    fn as_analog(self: Self) -> Self::Analog {
+                        // In DISABLED mode, DOUT enables the pull-up resistor
+                        self.configure(|w| w.$modei().disabled(), false)
+                    }
                 }
+
+                #[cfg(feature = "chip-efm32gg")]
+                impl analog::sealed::Sealed for $PXi<Analog> {}
             )+
         }
+    }
+}
 
+// The pins that exist on any package of the chip.
+
+#[cfg(feature = "chip-efm32gg")]
+gpio!([
+    PA0:  (pa0,  0,  pa_din, pa_dout, pa_douttgl, mode0,  pa_model, PA, 0),
+    PA1:  (pa1,  1,  pa_din, pa_dout, pa_douttgl, mode1,  pa_model, PA, 0),
+    PA2:  (pa2,  2,  pa_din, pa_dout, pa_douttgl, mode2,  pa_model, PA, 0),
+    PA3:  (pa3,  3,  pa_din, pa_dout, pa_douttgl, mode3,  pa_model, PA, 0),
+    PA4:  (pa4,  4,  pa_din, pa_dout, pa_douttgl, mode4,  pa_model, PA, 0),
+    PA5:  (pa5,  5,  pa_din, pa_dout, pa_douttgl, mode5,  pa_model, PA, 0),
+    PA6:  (pa6,  6,  pa_din, pa_dout, pa_douttgl, mode6,  pa_model, PA, 0),
+    PA7:  (pa7,  7,  pa_din, pa_dout, pa_douttgl, mode7,  pa_model, PA, 0),
+    PA8:  (pa8,  8,  pa_din, pa_dout, pa_douttgl, mode8,  pa_modeh, PA, 0),
+    PA9:  (pa9,  9,  pa_din, pa_dout, pa_douttgl, mode9,  pa_modeh, PA, 0),
+    PA10: (pa10, 10, pa_din, pa_dout, pa_douttgl, mode10, pa_modeh, PA, 0),
+    PA11: (pa11, 11, pa_din, pa_dout, pa_douttgl, mode11, pa_modeh, PA, 0),
+    PA12: (pa12, 12, pa_din, pa_dout, pa_douttgl, mode12, pa_modeh, PA, 0),
+    PA13: (pa13, 13, pa_din, pa_dout, pa_douttgl, mode13, pa_modeh, PA, 0),
+    PA14: (pa14, 14, pa_din, pa_dout, pa_douttgl, mode14, pa_modeh, PA, 0),
+    PA15: (pa15, 15, pa_din, pa_dout, pa_douttgl, mode15, pa_modeh, PA, 0),
+    PB0:  (pb0,  0,  pb_din, pb_dout, pb_douttgl, mode0,  pb_model, PB, 1),
+    PB1:  (pb1,  1,  pb_din, pb_dout, pb_douttgl, mode1,  pb_model, PB, 1),
+    PB2:  (pb2,  2,  pb_din, pb_dout, pb_douttgl, mode2,  pb_model, PB, 1),
+    PB3:  (pb3,  3,  pb_din, pb_dout, pb_douttgl, mode3,  pb_model, PB, 1),
+    PB4:  (pb4,  4,  pb_din, pb_dout, pb_douttgl, mode4,  pb_model, PB, 1),
+    PB5:  (pb5,  5,  pb_din, pb_dout, pb_douttgl, mode5,  pb_model, PB, 1),
+    PB6:  (pb6,  6,  pb_din, pb_dout, pb_douttgl, mode6,  pb_model, PB, 1),
+    PB7:  (pb7,  7,  pb_din, pb_dout, pb_douttgl, mode7,  pb_model, PB, 1),
+    PB8:  (pb8,  8,  pb_din, pb_dout, pb_douttgl, mode8,  pb_modeh, PB, 1),
+    PB9:  (pb9,  9,  pb_din, pb_dout, pb_douttgl, mode9,  pb_modeh, PB, 1),
+    PB10: (pb10, 10, pb_din, pb_dout, pb_douttgl, mode10, pb_modeh, PB, 1),
+    PB11: (pb11, 11, pb_din, pb_dout, pb_douttgl, mode11, pb_modeh, PB, 1),
+    PB12: (pb12, 12, pb_din, pb_dout, pb_douttgl, mode12, pb_modeh, PB, 1),
+    PB13: (pb13, 13, pb_din, pb_dout, pb_douttgl, mode13, pb_modeh, PB, 1),
+    PB14: (pb14, 14, pb_din, pb_dout, pb_douttgl, mode14, pb_modeh, PB, 1),
+    PB15: (pb15, 15, pb_din, pb_dout, pb_douttgl, mode15, pb_modeh, PB, 1),
+    PC0:  (pc0,  0,  pc_din, pc_dout, pc_douttgl, mode0,  pc_model, PC, 2),
+    PC1:  (pc1,  1,  pc_din, pc_dout, pc_douttgl, mode1,  pc_model, PC, 2),
+    PC2:  (pc2,  2,  pc_din, pc_dout, pc_douttgl, mode2,  pc_model, PC, 2),
+    PC3:  (pc3,  3,  pc_din, pc_dout, pc_douttgl, mode3,  pc_model, PC, 2),
+    PC4:  (pc4,  4,  pc_din, pc_dout, pc_douttgl, mode4,  pc_model, PC, 2),
+    PC5:  (pc5,  5,  pc_din, pc_dout, pc_douttgl, mode5,  pc_model, PC, 2),
+    PC6:  (pc6,  6,  pc_din, pc_dout, pc_douttgl, mode6,  pc_model, PC, 2),
+    PC7:  (pc7,  7,  pc_din, pc_dout, pc_douttgl, mode7,  pc_model, PC, 2),
+    PC8:  (pc8,  8,  pc_din, pc_dout, pc_douttgl, mode8,  pc_modeh, PC, 2),
+    PC9:  (pc9,  9,  pc_din, pc_dout, pc_douttgl, mode9,  pc_modeh, PC, 2),
+    PC10: (pc10, 10, pc_din, pc_dout, pc_douttgl, mode10, pc_modeh, PC, 2),
+    PC11: (pc11, 11, pc_din, pc_dout, pc_douttgl, mode11, pc_modeh, PC, 2),
+    PC12: (pc12, 12, pc_din, pc_dout, pc_douttgl, mode12, pc_modeh, PC, 2),
+    PC13: (pc13, 13, pc_din, pc_dout, pc_douttgl, mode13, pc_modeh, PC, 2),
+    PC14: (pc14, 14, pc_din, pc_dout, pc_douttgl, mode14, pc_modeh, PC, 2),
+    PC15: (pc15, 15, pc_din, pc_dout, pc_douttgl, mode15, pc_modeh, PC, 2),
+    PD0:  (pd0,  0,  pd_din, pd_dout, pd_douttgl, mode0,  pd_model, PD, 3),
+    PD1:  (pd1,  1,  pd_din, pd_dout, pd_douttgl, mode1,  pd_model, PD, 3),
+    PD2:  (pd2,  2,  pd_din, pd_dout, pd_douttgl, mode2,  pd_model, PD, 3),
+    PD3:  (pd3,  3,  pd_din, pd_dout, pd_douttgl, mode3,  pd_model, PD, 3),
+    PD4:  (pd4,  4,  pd_din, pd_dout, pd_douttgl, mode4,  pd_model, PD, 3),
+    PD5:  (pd5,  5,  pd_din, pd_dout, pd_douttgl, mode5,  pd_model, PD, 3),
+    PD6:  (pd6,  6,  pd_din, pd_dout, pd_douttgl, mode6,  pd_model, PD, 3),
+    PD7:  (pd7,  7,  pd_din, pd_dout, pd_douttgl, mode7,  pd_model, PD, 3),
+    PD8:  (pd8,  8,  pd_din, pd_dout, pd_douttgl, mode8,  pd_modeh, PD, 3),
+    PD9:  (pd9,  9,  pd_din, pd_dout, pd_douttgl, mode9,  pd_modeh, PD, 3),
+    PD10: (pd10, 10, pd_din, pd_dout, pd_douttgl, mode10, pd_modeh, PD, 3),
+    PD11: (pd11, 11, pd_din, pd_dout, pd_douttgl, mode11, pd_modeh, PD, 3),
+    PD12: (pd12, 12, pd_din, pd_dout, pd_douttgl, mode12, pd_modeh, PD, 3),
+    PD13: (pd13, 13, pd_din, pd_dout, pd_douttgl, mode13, pd_modeh, PD, 3),
+    PD14: (pd14, 14, pd_din, pd_dout, pd_douttgl, mode14, pd_modeh, PD, 3),
+    PD15: (pd15, 15, pd_din, pd_dout, pd_douttgl, mode15, pd_modeh, PD, 3),
+    PE0:  (pe0,  0,  pe_din, pe_dout, pe_douttgl, mode0,  pe_model, PE, 4),
+    PE1:  (pe1,  1,  pe_din, pe_dout, pe_douttgl, mode1,  pe_model, PE, 4),
+    PE2:  (pe2,  2,  pe_din, pe_dout, pe_douttgl, mode2,  pe_model, PE, 4),
+    PE3:  (pe3,  3,  pe_din, pe_dout, pe_douttgl, mode3,  pe_model, PE, 4),
+    PE4:  (pe4,  4,  pe_din, pe_dout, pe_douttgl, mode4,  pe_model, PE, 4),
+    PE5:  (pe5,  5,  pe_din, pe_dout, pe_douttgl, mode5,  pe_model, PE, 4),
+    PE6:  (pe6,  6,  pe_din, pe_dout, pe_douttgl, mode6,  pe_model, PE, 4),
+    PE7:  (pe7,  7,  pe_din, pe_dout, pe_douttgl, mode7,  pe_model, PE, 4),
+    PE8:  (pe8,  8,  pe_din, pe_dout, pe_douttgl, mode8,  pe_modeh, PE, 4),
+    PE9:  (pe9,  9,  pe_din, pe_dout, pe_douttgl, mode9,  pe_modeh, PE, 4),
+    PE10: (pe10, 10, pe_din, pe_dout, pe_douttgl, mode10, pe_modeh, PE, 4),
+    PE11: (pe11, 11, pe_din, pe_dout, pe_douttgl, mode11, pe_modeh, PE, 4),
+    PE12: (pe12, 12, pe_din, pe_dout, pe_douttgl, mode12, pe_modeh, PE, 4),
+    PE13: (pe13, 13, pe_din, pe_dout, pe_douttgl, mode13, pe_modeh, PE, 4),
+    PE14: (pe14, 14, pe_din, pe_dout, pe_douttgl, mode14, pe_modeh, PE, 4),
+    PE15: (pe15, 15, pe_din, pe_dout, pe_douttgl, mode15, pe_modeh, PE, 4),
+    PF0:  (pf0,  0,  pf_din, pf_dout, pf_douttgl, mode0,  pf_model, PF, 5),
+    PF1:  (pf1,  1,  pf_din, pf_dout, pf_douttgl, mode1,  pf_model, PF, 5),
+    PF2:  (pf2,  2,  pf_din, pf_dout, pf_douttgl, mode2,  pf_model, PF, 5),
+    PF3:  (pf3,  3,  pf_din, pf_dout, pf_douttgl, mode3,  pf_model, PF, 5),
+    PF4:  (pf4,  4,  pf_din, pf_dout, pf_douttgl, mode4,  pf_model, PF, 5),
+    PF5:  (pf5,  5,  pf_din, pf_dout, pf_douttgl, mode5,  pf_model, PF, 5),
+    PF6:  (pf6,  6,  pf_din, pf_dout, pf_douttgl, mode6,  pf_model, PF, 5),
+    PF7:  (pf7,  7,  pf_din, pf_dout, pf_douttgl, mode7,  pf_model, PF, 5),
+    PF8:  (pf8,  8,  pf_din, pf_dout, pf_douttgl, mode8,  pf_modeh, PF, 5),
+    PF9:  (pf9,  9,  pf_din, pf_dout, pf_douttgl, mode9,  pf_modeh, PF, 5),
+    PF10: (pf10, 10, pf_din, pf_dout, pf_douttgl, mode10, pf_modeh, PF, 5),
+    PF11: (pf11, 11, pf_din, pf_dout, pf_douttgl, mode11, pf_modeh, PF, 5),
+    PF12: (pf12, 12, pf_din, pf_dout, pf_douttgl, mode12, pf_modeh, PF, 5),
+]);
+
+#[cfg(feature = "chip-efr32xg1")]
+gpio!([
+    PA0:  (pa0,  0,  pa_din, pa_dout, pa_douttgl, mode0,  pa_model, PA, 0),
+    PA1:  (pa1,  1,  pa_din, pa_dout, pa_douttgl, mode1,  pa_model, PA, 0),
+    PA2:  (pa2,  2,  pa_din, pa_dout, pa_douttgl, mode2,  pa_model, PA, 0),
+    PA3:  (pa3,  3,  pa_din, pa_dout, pa_douttgl, mode3,  pa_model, PA, 0),
+    PA4:  (pa4,  4,  pa_din, pa_dout, pa_douttgl, mode4,  pa_model, PA, 0),
+    PA5:  (pa5,  5,  pa_din, pa_dout, pa_douttgl, mode5,  pa_model, PA, 0),
+    PB11: (pb11, 11, pb_din, pb_dout, pb_douttgl, mode11, pb_modeh, PB, 1),
+    PB12: (pb12, 12, pb_din, pb_dout, pb_douttgl, mode12, pb_modeh, PB, 1),
+    PB13: (pb13, 13, pb_din, pb_dout, pb_douttgl, mode13, pb_modeh, PB, 1),
+    PB14: (pb14, 14, pb_din, pb_dout, pb_douttgl, mode14, pb_modeh, PB, 1),
+    PB15: (pb15, 15, pb_din, pb_dout, pb_douttgl, mode15, pb_modeh, PB, 1),
+    PC6:  (pc6,  6,  pc_din, pc_dout, pc_douttgl, mode6,  pc_model, PC, 2),
+    PC7:  (pc7,  7,  pc_din, pc_dout, pc_douttgl, mode7,  pc_model, PC, 2),
+    PC8:  (pc8,  8,  pc_din, pc_dout, pc_douttgl, mode8,  pc_modeh, PC, 2),
+    PC9:  (pc9,  9,  pc_din, pc_dout, pc_douttgl, mode9,  pc_modeh, PC, 2),
+    PC10: (pc10, 10, pc_din, pc_dout, pc_douttgl, mode10, pc_modeh, PC, 2),
+    PC11: (pc11, 11, pc_din, pc_dout, pc_douttgl, mode11, pc_modeh, PC, 2),
+    PD9:  (pd9,  9,  pd_din, pd_dout, pd_douttgl, mode9,  pd_modeh, PD, 3),
+    PD10: (pd10, 10, pd_din, pd_dout, pd_douttgl, mode10, pd_modeh, PD, 3),
+    PD11: (pd11, 11, pd_din, pd_dout, pd_douttgl, mode11, pd_modeh, PD, 3),
+    PD12: (pd12, 12, pd_din, pd_dout, pd_douttgl, mode12, pd_modeh, PD, 3),
+    PD13: (pd13, 13, pd_din, pd_dout, pd_douttgl, mode13, pd_modeh, PD, 3),
+    PD14: (pd14, 14, pd_din, pd_dout, pd_douttgl, mode14, pd_modeh, PD, 3),
+    PD15: (pd15, 15, pd_din, pd_dout, pd_douttgl, mode15, pd_modeh, PD, 3),
+    PF0:  (pf0,  0,  pf_din, pf_dout, pf_douttgl, mode0,  pf_model, PF, 5),
+    PF1:  (pf1,  1,  pf_din, pf_dout, pf_douttgl, mode1,  pf_model, PF, 5),
+    PF2:  (pf2,  2,  pf_din, pf_dout, pf_douttgl, mode2,  pf_model, PF, 5),
+    PF3:  (pf3,  3,  pf_din, pf_dout, pf_douttgl, mode3,  pf_model, PF, 5),
+    PF4:  (pf4,  4,  pf_din, pf_dout, pf_douttgl, mode4,  pf_model, PF, 5),
+    PF5:  (pf5,  5,  pf_din, pf_dout, pf_douttgl, mode5,  pf_model, PF, 5),
+    PF6:  (pf6,  6,  pf_din, pf_dout, pf_douttgl, mode6,  pf_model, PF, 5),
+    PF7:  (pf7,  7,  pf_din, pf_dout, pf_douttgl, mode7,  pf_model, PF, 5),
+]);
+
+macro_rules! pins {
+    ($($pxi:ident: $PXi:ident,)+) => {
         pub struct Pins {
             $(
                 pub $pxi: pins::$PXi<Disabled>,
             )+
+            pub em4: EM4,
+            pub ports: Ports,
+            pub exti: ExtiLines,
+            pub lock: ConfigLock,
+            pub debug: DebugPins,
         }
 
         impl GPIOExt for registers::GPIO {
@@ -166,107 +1501,146 @@ macro_rules! gpio {
                     $(
                         $pxi: pins::$PXi { _mode: PhantomData },
                     )+
+                    em4: EM4 { _private: () },
+                    ports: Ports::new(),
+                    exti: ExtiLines::new(),
+                    lock: ConfigLock { _private: () },
+                    debug: DebugPins { _private: () },
                 }
             }
         }
     }
 }
 
-gpio!([
-    PA0:  (pa0,  0,  pa_din, pa_dout, mode0,  pa_model),
-    PA1:  (pa1,  1,  pa_din, pa_dout, mode1,  pa_model),
-    PA2:  (pa2,  2,  pa_din, pa_dout, mode2,  pa_model),
-    PA3:  (pa3,  3,  pa_din, pa_dout, mode3,  pa_model),
-    PA4:  (pa4,  4,  pa_din, pa_dout, mode4,  pa_model),
-    PA5:  (pa5,  5,  pa_din, pa_dout, mode5,  pa_model),
-    PA6:  (pa6,  6,  pa_din, pa_dout, mode6,  pa_model),
-    PA7:  (pa7,  7,  pa_din, pa_dout, mode7,  pa_model),
-    PA8:  (pa8,  8,  pa_din, pa_dout, mode8,  pa_modeh),
-    PA9:  (pa9,  9,  pa_din, pa_dout, mode9,  pa_modeh),
-    PA10: (pa10, 10, pa_din, pa_dout, mode10, pa_modeh),
-    PA11: (pa11, 11, pa_din, pa_dout, mode11, pa_modeh),
-    PA12: (pa12, 12, pa_din, pa_dout, mode12, pa_modeh),
-    PA13: (pa13, 13, pa_din, pa_dout, mode13, pa_modeh),
-    PA14: (pa14, 14, pa_din, pa_dout, mode14, pa_modeh),
-    PA15: (pa15, 15, pa_din, pa_dout, mode15, pa_modeh),
-    PB0:  (pb0,  0,  pb_din, pb_dout, mode0,  pb_model),
-    PB1:  (pb1,  1,  pb_din, pb_dout, mode1,  pb_model),
-    PB2:  (pb2,  2,  pb_din, pb_dout, mode2,  pb_model),
-    PB3:  (pb3,  3,  pb_din, pb_dout, mode3,  pb_model),
-    PB4:  (pb4,  4,  pb_din, pb_dout, mode4,  pb_model),
-    PB5:  (pb5,  5,  pb_din, pb_dout, mode5,  pb_model),
-    PB6:  (pb6,  6,  pb_din, pb_dout, mode6,  pb_model),
-    PB7:  (pb7,  7,  pb_din, pb_dout, mode7,  pb_model),
-    PB8:  (pb8,  8,  pb_din, pb_dout, mode8,  pb_modeh),
-    PB9:  (pb9,  9,  pb_din, pb_dout, mode9,  pb_modeh),
-    PB10: (pb10, 10, pb_din, pb_dout, mode10, pb_modeh),
-    PB11: (pb11, 11, pb_din, pb_dout, mode11, pb_modeh),
-    PB12: (pb12, 12, pb_din, pb_dout, mode12, pb_modeh),
-    PB13: (pb13, 13, pb_din, pb_dout, mode13, pb_modeh),
-    PB14: (pb14, 14, pb_din, pb_dout, mode14, pb_modeh),
-    PB15: (pb15, 15, pb_din, pb_dout, mode15, pb_modeh),
-    PC0:  (pc0,  0,  pc_din, pc_dout, mode0,  pc_model),
-    PC1:  (pc1,  1,  pc_din, pc_dout, mode1,  pc_model),
-    PC2:  (pc2,  2,  pc_din, pc_dout, mode2,  pc_model),
-    PC3:  (pc3,  3,  pc_din, pc_dout, mode3,  pc_model),
-    PC4:  (pc4,  4,  pc_din, pc_dout, mode4,  pc_model),
-    PC5:  (pc5,  5,  pc_din, pc_dout, mode5,  pc_model),
-    PC6:  (pc6,  6,  pc_din, pc_dout, mode6,  pc_model),
-    PC7:  (pc7,  7,  pc_din, pc_dout, mode7,  pc_model),
-    PC8:  (pc8,  8,  pc_din, pc_dout, mode8,  pc_modeh),
-    PC9:  (pc9,  9,  pc_din, pc_dout, mode9,  pc_modeh),
-    PC10: (pc10, 10, pc_din, pc_dout, mode10, pc_modeh),
-    PC11: (pc11, 11, pc_din, pc_dout, mode11, pc_modeh),
-    PC12: (pc12, 12, pc_din, pc_dout, mode12, pc_modeh),
-    PC13: (pc13, 13, pc_din, pc_dout, mode13, pc_modeh),
-    PC14: (pc14, 14, pc_din, pc_dout, mode14, pc_modeh),
-    PC15: (pc15, 15, pc_din, pc_dout, mode15, pc_modeh),
-    PD0:  (pd0,  0,  pd_din, pd_dout, mode0,  pd_model),
-    PD1:  (pd1,  1,  pd_din, pd_dout, mode1,  pd_model),
-    PD2:  (pd2,  2,  pd_din, pd_dout, mode2,  pd_model),
-    PD3:  (pd3,  3,  pd_din, pd_dout, mode3,  pd_model),
-    PD4:  (pd4,  4,  pd_din, pd_dout, mode4,  pd_model),
-    PD5:  (pd5,  5,  pd_din, pd_dout, mode5,  pd_model),
-    PD6:  (pd6,  6,  pd_din, pd_dout, mode6,  pd_model),
-    PD7:  (pd7,  7,  pd_din, pd_dout, mode7,  pd_model),
-    PD8:  (pd8,  8,  pd_din, pd_dout, mode8,  pd_modeh),
-    PD9:  (pd9,  9,  pd_din, pd_dout, mode9,  pd_modeh),
-    PD10: (pd10, 10, pd_din, pd_dout, mode10, pd_modeh),
-    PD11: (pd11, 11, pd_din, pd_dout, mode11, pd_modeh),
-    PD12: (pd12, 12, pd_din, pd_dout, mode12, pd_modeh),
-    PD13: (pd13, 13, pd_din, pd_dout, mode13, pd_modeh),
-    PD14: (pd14, 14, pd_din, pd_dout, mode14, pd_modeh),
-    PD15: (pd15, 15, pd_din, pd_dout, mode15, pd_modeh),
-    PE0:  (pe0,  0,  pe_din, pe_dout, mode0,  pe_model),
-    PE1:  (pe1,  1,  pe_din, pe_dout, mode1,  pe_model),
-    PE2:  (pe2,  2,  pe_din, pe_dout, mode2,  pe_model),
-    PE3:  (pe3,  3,  pe_din, pe_dout, mode3,  pe_model),
-    PE4:  (pe4,  4,  pe_din, pe_dout, mode4,  pe_model),
-    PE5:  (pe5,  5,  pe_din, pe_dout, mode5,  pe_model),
-    PE6:  (pe6,  6,  pe_din, pe_dout, mode6,  pe_model),
-    PE7:  (pe7,  7,  pe_din, pe_dout, mode7,  pe_model),
-    PE8:  (pe8,  8,  pe_din, pe_dout, mode8,  pe_modeh),
-    PE9:  (pe9,  9,  pe_din, pe_dout, mode9,  pe_modeh),
-    PE10: (pe10, 10, pe_din, pe_dout, mode10, pe_modeh),
-    PE11: (pe11, 11, pe_din, pe_dout, mode11, pe_modeh),
-    PE12: (pe12, 12, pe_din, pe_dout, mode12, pe_modeh),
-    PE13: (pe13, 13, pe_din, pe_dout, mode13, pe_modeh),
-    PE14: (pe14, 14, pe_din, pe_dout, mode14, pe_modeh),
-    PE15: (pe15, 15, pe_din, pe_dout, mode15, pe_modeh),
-    PF0:  (pf0,  0,  pf_din, pf_dout, mode0,  pf_model),
-    PF1:  (pf1,  1,  pf_din, pf_dout, mode1,  pf_model),
-    PF2:  (pf2,  2,  pf_din, pf_dout, mode2,  pf_model),
-    PF3:  (pf3,  3,  pf_din, pf_dout, mode3,  pf_model),
-    PF4:  (pf4,  4,  pf_din, pf_dout, mode4,  pf_model),
-    PF5:  (pf5,  5,  pf_din, pf_dout, mode5,  pf_model),
-    PF6:  (pf6,  6,  pf_din, pf_dout, mode6,  pf_model),
-    PF7:  (pf7,  7,  pf_din, pf_dout, mode7,  pf_model),
-    PF8:  (pf8,  8,  pf_din, pf_dout, mode8,  pf_modeh),
-    PF9:  (pf9,  9,  pf_din, pf_dout, mode9,  pf_modeh),
-    PF10: (pf10, 10, pf_din, pf_dout, mode10, pf_modeh),
-    PF11: (pf11, 11, pf_din, pf_dout, mode11, pf_modeh),
-    PF12: (pf12, 12, pf_din, pf_dout, mode12, pf_modeh),
-    PF13: (pf13, 13, pf_din, pf_dout, mode13, pf_modeh),
-    PF14: (pf14, 14, pf_din, pf_dout, mode14, pf_modeh),
-    PF15: (pf15, 15, pf_din, pf_dout, mode15, pf_modeh),
-]);
+// The pins handed out by the chip, except for those of the debug interface (see DebugPins).
+//
+// These are the pins of the chip's largest package (BGA120 on EFM32GG, QFN48 on EFR32xG1). Smaller
+// packages lack some of them; telling them apart would need per-package features selecting
+// reduced tables here, which are not described yet.
+
+#[cfg(feature = "chip-efm32gg")]
+pins!(
+    pa0:  PA0,
+    pa1:  PA1,
+    pa2:  PA2,
+    pa3:  PA3,
+    pa4:  PA4,
+    pa5:  PA5,
+    pa6:  PA6,
+    pa7:  PA7,
+    pa8:  PA8,
+    pa9:  PA9,
+    pa10: PA10,
+    pa11: PA11,
+    pa12: PA12,
+    pa13: PA13,
+    pa14: PA14,
+    pa15: PA15,
+    pb0:  PB0,
+    pb1:  PB1,
+    pb2:  PB2,
+    pb3:  PB3,
+    pb4:  PB4,
+    pb5:  PB5,
+    pb6:  PB6,
+    pb7:  PB7,
+    pb8:  PB8,
+    pb9:  PB9,
+    pb10: PB10,
+    pb11: PB11,
+    pb12: PB12,
+    pb13: PB13,
+    pb14: PB14,
+    pb15: PB15,
+    pc0:  PC0,
+    pc1:  PC1,
+    pc2:  PC2,
+    pc3:  PC3,
+    pc4:  PC4,
+    pc5:  PC5,
+    pc6:  PC6,
+    pc7:  PC7,
+    pc8:  PC8,
+    pc9:  PC9,
+    pc10: PC10,
+    pc11: PC11,
+    pc12: PC12,
+    pc13: PC13,
+    pc14: PC14,
+    pc15: PC15,
+    pd0:  PD0,
+    pd1:  PD1,
+    pd2:  PD2,
+    pd3:  PD3,
+    pd4:  PD4,
+    pd5:  PD5,
+    pd6:  PD6,
+    pd7:  PD7,
+    pd8:  PD8,
+    pd9:  PD9,
+    pd10: PD10,
+    pd11: PD11,
+    pd12: PD12,
+    pd13: PD13,
+    pd14: PD14,
+    pd15: PD15,
+    pe0:  PE0,
+    pe1:  PE1,
+    pe2:  PE2,
+    pe3:  PE3,
+    pe4:  PE4,
+    pe5:  PE5,
+    pe6:  PE6,
+    pe7:  PE7,
+    pe8:  PE8,
+    pe9:  PE9,
+    pe10: PE10,
+    pe11: PE11,
+    pe12: PE12,
+    pe13: PE13,
+    pe14: PE14,
+    pe15: PE15,
+    pf2:  PF2,
+    pf3:  PF3,
+    pf4:  PF4,
+    pf5:  PF5,
+    pf6:  PF6,
+    pf7:  PF7,
+    pf8:  PF8,
+    pf9:  PF9,
+    pf10: PF10,
+    pf11: PF11,
+    pf12: PF12,
+);
+
+#[cfg(feature = "chip-efr32xg1")]
+pins!(
+    pa0:  PA0,
+    pa1:  PA1,
+    pa2:  PA2,
+    pa3:  PA3,
+    pa4:  PA4,
+    pa5:  PA5,
+    pb11: PB11,
+    pb12: PB12,
+    pb13: PB13,
+    pb14: PB14,
+    pb15: PB15,
+    pc6:  PC6,
+    pc7:  PC7,
+    pc8:  PC8,
+    pc9:  PC9,
+    pc10: PC10,
+    pc11: PC11,
+    pd9:  PD9,
+    pd10: PD10,
+    pd11: PD11,
+    pd12: PD12,
+    pd13: PD13,
+    pd14: PD14,
+    pd15: PD15,
+    pf4:  PF4,
+    pf5:  PF5,
+    pf6:  PF6,
+    pf7:  PF7,
+);
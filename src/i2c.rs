@@ -17,6 +17,7 @@ use embedded_hal;
 use registers;
 
 use super::cmu;
+use super::emu;
 use crate::gpio::{pins, Disabled, EFM32Pin};
 
 pub trait I2CExt<Clk, WithClock> {
@@ -29,7 +30,7 @@ impl I2CExt<cmu::I2C0Clk, I2C0WithClock> for registers::I2C0 {
         self.clkdiv.write(|w| unsafe { w.div().bits(20) });
 
         self.ctrl.write(|w| w.en().bit(true));
-        I2C0WithClock { reg: self }
+        I2C0WithClock { reg: self, clock }
     }
 }
 
@@ -38,6 +39,7 @@ impl I2CExt<cmu::I2C0Clk, I2C0WithClock> for registers::I2C0 {
 // clocks, theose structs can stay as small as they are.
 pub struct I2C0WithClock {
     reg: registers::I2C0,
+    clock: cmu::I2C0Clk,
 }
 
 impl I2C0WithClock {
@@ -53,6 +55,7 @@ impl I2C0WithClock {
 
         Ok(I2C0WithScl {
             reg: self.reg,
+            clock: self.clock,
             sclroute: route,
             sclpin: scl,
         })
@@ -62,6 +65,7 @@ impl I2C0WithClock {
 
 pub struct I2C0WithScl<SCLP: EFM32Pin> {
     reg: registers::I2C0,
+    clock: cmu::I2C0Clk,
     // Carried around briefly in the struct so they can be enabled in the right sequence in the end
     sclpin: SCLP,
     sclroute: registers::i2c0::routeloc0::SCLLOCW,
@@ -91,13 +95,16 @@ impl<SCLP: EFM32Pin> I2C0WithScl<SCLP> {
 
         self.reg.cmd.write(|w| w.abort().bit(true));
 
-        Ok(ConfiguredI2C0 { reg: self.reg })
+        emu::block(emu::Blocker::I2C0);
+
+        Ok(ConfiguredI2C0 { reg: self.reg, clock: self.clock })
     }
     // Same would need duplication
 }
 
 pub struct ConfiguredI2C0 {
     reg: registers::I2C0,
+    clock: cmu::I2C0Clk,
 }
 
 /// Error conditions a read or write operation can end with. Some of those states can happen
@@ -118,6 +125,18 @@ pub enum Error {
 }
 
 impl ConfiguredI2C0 {
+    /// Disable the I2C peripheral and release its register block and clock.
+    ///
+    /// The clock gate is disabled, so the clock can be given back to thaw its branch. Until then,
+    /// the configured I2C keeps the device from entering energy modes below EM1. Its pins stay
+    /// routed.
+    pub fn free(mut self) -> (registers::I2C0, cmu::I2C0Clk) {
+        self.reg.ctrl.write(|w| w.en().bit(false));
+        self.clock.disable();
+        emu::unblock(emu::Blocker::I2C0);
+        (self.reg, self.clock)
+    }
+
     /// Set stop condition on bus and wait for bus to return to idle (or busy, if someone else
     /// starts talking just as we release) state.
     fn stop_and_finish(&mut self) {
@@ -2,6 +2,8 @@
 
 extern crate cortex_m;
 extern crate embedded_hal;
+extern crate nb;
+extern crate void;
 
 #[cfg(feature = "chip-efm32gg")]
 extern crate efm32gg990 as registers;
@@ -11,6 +13,7 @@ extern crate efr32xg1 as registers;
 pub mod time_util;
 
 pub mod cmu;
+pub mod emu;
 pub mod gpio;
 
 // Right now that's implemented only there, and does not have the internal cfgs yet to run on
@@ -22,5 +25,6 @@ pub mod systick;
 pub mod timer;
 
 mod bitband;
+mod devinfo;
 
 mod routing;
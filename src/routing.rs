@@ -13,6 +13,15 @@ pub trait HasLocForFunction<P, F> {
     unsafe fn deconfigure();
 }
 
+/// Like HasLocForFunction, but for pins configured as inputs to the function (eg. for
+/// TimerChannel::capture)
+pub trait HasInputLocForFunction<P, F> {
+    /// Configure and enable the routed pin
+    unsafe fn configure();
+    /// Disable the routed pin
+    unsafe fn deconfigure();
+}
+
 mod per_function;
 mod per_peripheral;
 
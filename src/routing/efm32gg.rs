@@ -4,7 +4,8 @@
 //! just add it.)
 
 use crate::timer::{Timer0, Timer1, Timer2, Timer3, Channel0, Channel1, Channel2};
-use super::per_peripheral::timerperipheral_pin;
+use crate::cmu::{ClkOut0, ClkOut1};
+use super::per_peripheral::{timerperipheral_pin, clkoutperipheral_pin};
 
 // Routes with pins on duplicate routes still need some macro adjustment
 
@@ -63,3 +64,11 @@ timerperipheral_pin!(Timer3, Channel1, PE1, is_loc1, cc1pen);
 
 timerperipheral_pin!(Timer3, Channel2, PA15, is_loc0, cc2pen);
 timerperipheral_pin!(Timer3, Channel2, PE2, is_loc1, cc2pen);
+
+clkoutperipheral_pin!(ClkOut0, PA2, loc0, is_loc0, clkout0pen, clkout1pen);
+clkoutperipheral_pin!(ClkOut0, PC12, loc1, is_loc1, clkout0pen, clkout1pen);
+clkoutperipheral_pin!(ClkOut0, PD7, loc2, is_loc2, clkout0pen, clkout1pen);
+
+clkoutperipheral_pin!(ClkOut1, PA1, loc0, is_loc0, clkout1pen, clkout0pen);
+clkoutperipheral_pin!(ClkOut1, PD8, loc1, is_loc1, clkout1pen, clkout0pen);
+clkoutperipheral_pin!(ClkOut1, PE12, loc2, is_loc2, clkout1pen, clkout0pen);
@@ -3,10 +3,29 @@
 //! Blue Gecko appear to be the same.
 
 use crate::timer::{Timer0, Channel0, Channel1, Channel2};
-use super::per_function::timerchannel_pin;
+use crate::cmu::{ClkOut0, ClkOut1};
+use super::per_function::{timerchannel_pin, clkoutchannel_pin};
 
 timerchannel_pin!(Timer0, Channel0, PA0, loc0, cc0loc, cc0pen);
 
 timerchannel_pin!(Timer0, Channel0, PD11, loc19, cc0loc, cc0pen);
 timerchannel_pin!(Timer0, Channel1, PD12, loc19, cc1loc, cc1pen);
 timerchannel_pin!(Timer0, Channel2, PD13, loc19, cc2loc, cc2pen);
+
+clkoutchannel_pin!(ClkOut0, PA1, loc0, clkout0loc, clkout0pen);
+clkoutchannel_pin!(ClkOut0, PB15, loc1, clkout0loc, clkout0pen);
+clkoutchannel_pin!(ClkOut0, PC6, loc2, clkout0loc, clkout0pen);
+clkoutchannel_pin!(ClkOut0, PC11, loc3, clkout0loc, clkout0pen);
+clkoutchannel_pin!(ClkOut0, PD9, loc4, clkout0loc, clkout0pen);
+clkoutchannel_pin!(ClkOut0, PD14, loc5, clkout0loc, clkout0pen);
+clkoutchannel_pin!(ClkOut0, PF2, loc6, clkout0loc, clkout0pen);
+clkoutchannel_pin!(ClkOut0, PF7, loc7, clkout0loc, clkout0pen);
+
+clkoutchannel_pin!(ClkOut1, PA0, loc0, clkout1loc, clkout1pen);
+clkoutchannel_pin!(ClkOut1, PB14, loc1, clkout1loc, clkout1pen);
+clkoutchannel_pin!(ClkOut1, PC7, loc2, clkout1loc, clkout1pen);
+clkoutchannel_pin!(ClkOut1, PC10, loc3, clkout1loc, clkout1pen);
+clkoutchannel_pin!(ClkOut1, PD10, loc4, clkout1loc, clkout1pen);
+clkoutchannel_pin!(ClkOut1, PD15, loc5, clkout1loc, clkout1pen);
+clkoutchannel_pin!(ClkOut1, PF3, loc6, clkout1loc, clkout1pen);
+clkoutchannel_pin!(ClkOut1, PF6, loc7, clkout1loc, clkout1pen);
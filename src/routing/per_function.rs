@@ -2,7 +2,7 @@
 macro_rules! timerchannel_pin {
     ($TimerN: ident, $ChannelX: ident, $Pin: ident, $locI: ident, $ccXloc: ident, $ccXpen: ident) => {
 
-impl super::HasLocForFunction<$TimerN, $ChannelX> for crate::gpio::pins::$Pin<crate::gpio::Output> {
+impl<Mode: crate::gpio::OutputMode> super::HasLocForFunction<$TimerN, $ChannelX> for crate::gpio::pins::$Pin<Mode> {
     unsafe fn configure() {
         // FIXME https://github.com/chrysn/efm32gg-hal/issues/1
         let reg = &mut *crate::timer::TimerChannel::<$TimerN, $ChannelX>::register();
@@ -21,7 +21,47 @@ impl super::HasLocForFunction<$TimerN, $ChannelX> for crate::gpio::pins::$Pin<cr
     }
 }
 
+// Input capture goes through the same route
+impl<Mode: crate::gpio::InputMode> super::HasInputLocForFunction<$TimerN, $ChannelX> for crate::gpio::pins::$Pin<Mode> {
+    unsafe fn configure() {
+        <crate::gpio::pins::$Pin<crate::gpio::Output> as super::HasLocForFunction<$TimerN, $ChannelX>>::configure()
+    }
+
+    unsafe fn deconfigure() {
+        <crate::gpio::pins::$Pin<crate::gpio::Output> as super::HasLocForFunction<$TimerN, $ChannelX>>::deconfigure()
+    }
+}
+
     }
 }
 
 pub(crate) use timerchannel_pin;
+
+#[macro_export]
+macro_rules! clkoutchannel_pin {
+    ($ClkOutN: ident, $Pin: ident, $locI: ident, $clkoutNloc: ident, $clkoutNpen: ident) => {
+
+impl super::HasLocForFunction<crate::cmu::Cmu, $ClkOutN> for crate::gpio::pins::$Pin<crate::gpio::Output> {
+    unsafe fn configure() {
+        // FIXME https://github.com/chrysn/efm32gg-hal/issues/1
+        let reg = &*registers::CMU::ptr();
+        cortex_m::interrupt::free(|_| {
+            reg.routeloc0.modify(|_, w| w.$clkoutNloc().$locI());
+            reg.routepen.modify(|_, w| w.$clkoutNpen().set_bit());
+        });
+    }
+
+    unsafe fn deconfigure() {
+        // FIXME https://github.com/chrysn/efm32gg-hal/issues/1
+        let reg = &*registers::CMU::ptr();
+        cortex_m::interrupt::free(|_| {
+            reg.routepen.modify(|_, w| w.$clkoutNpen().clear_bit());
+        });
+    }
+}
+
+    }
+}
+
+#[cfg(feature = "_routing_per_function")]
+pub(crate) use clkoutchannel_pin;
@@ -2,7 +2,7 @@
 macro_rules! timerperipheral_pin {
     ($TimerN: ident, $ChannelX: ident, $Pin: ident, $is_locI: ident, $ccXpen: ident) => {
 
-impl super::HasLocForFunction<$TimerN, $ChannelX> for crate::gpio::pins::$Pin<crate::gpio::Output> {
+impl<Mode: crate::gpio::OutputMode> super::HasLocForFunction<$TimerN, $ChannelX> for crate::gpio::pins::$Pin<Mode> {
     unsafe fn configure() {
         // This is safe because it's read-only access
         let reg = &mut *crate::timer::TimerChannel::<$TimerN, $ChannelX>::register();
@@ -23,7 +23,56 @@ impl super::HasLocForFunction<$TimerN, $ChannelX> for crate::gpio::pins::$Pin<cr
     }
 }
 
+// Input capture goes through the same route
+impl<Mode: crate::gpio::InputMode> super::HasInputLocForFunction<$TimerN, $ChannelX> for crate::gpio::pins::$Pin<Mode> {
+    unsafe fn configure() {
+        <crate::gpio::pins::$Pin<crate::gpio::Output> as super::HasLocForFunction<$TimerN, $ChannelX>>::configure()
+    }
+
+    unsafe fn deconfigure() {
+        <crate::gpio::pins::$Pin<crate::gpio::Output> as super::HasLocForFunction<$TimerN, $ChannelX>>::deconfigure()
+    }
+}
+
     }
 }
 
 pub(crate) use timerperipheral_pin;
+
+/// Like timerperipheral_pin, but for the CMU clock outputs, which share a single location.
+///
+/// Unlike with the timers, there are no more than two users of that location, so rather than
+/// requiring a pre-route, the location is set when the other output is not in use, and checked
+/// otherwise.
+#[macro_export]
+macro_rules! clkoutperipheral_pin {
+    ($ClkOutN: ident, $Pin: ident, $locI: ident, $is_locI: ident, $clkoutNpen: ident, $clkoutMpen: ident) => {
+
+impl super::HasLocForFunction<crate::cmu::Cmu, $ClkOutN> for crate::gpio::pins::$Pin<crate::gpio::Output> {
+    unsafe fn configure() {
+        let reg = &*registers::CMU::ptr();
+        // FIXME https://github.com/chrysn/efm32gg-hal/issues/1
+        cortex_m::interrupt::free(|_| {
+            if reg.route.read().$clkoutMpen().bit() {
+                assert!(reg.route.read().location().$is_locI(), "Pin conflicts with the other clock output's route");
+            } else {
+                reg.route.modify(|_, w| w.location().$locI());
+            }
+            reg.route.modify(|_, w| w.$clkoutNpen().set_bit());
+        });
+    }
+
+    unsafe fn deconfigure() {
+        // FIXME https://github.com/chrysn/efm32gg-hal/issues/1
+        let reg = &*registers::CMU::ptr();
+        cortex_m::interrupt::free(|_| {
+            reg.route.modify(|_, w| w.$clkoutNpen().clear_bit());
+        });
+    }
+}
+
+    }
+}
+
+#[cfg(not(feature = "_routing_per_function"))]
+pub(crate) use clkoutperipheral_pin;
@@ -48,6 +48,11 @@ impl SystickDelay {
 
         SystickDelay { systick, clock }
     }
+
+    /// Release the systick and the clock it was running from.
+    pub fn free(self) -> (Systick, HFCoreClk) {
+        (self.systick, self.clock)
+    }
 }
 
 impl<UXX> DelayUs<UXX> for SystickDelay
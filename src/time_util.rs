@@ -6,3 +6,39 @@
 /// Hertz
 #[derive(Clone, Copy)]
 pub struct Hertz(pub u32);
+
+/// Microseconds
+#[derive(Clone, Copy)]
+pub struct MicroSeconds(pub u32);
+
+/// Time between two periodic events, given either as their frequency or as a duration
+#[derive(Clone, Copy)]
+pub enum Period {
+    Frequency(Hertz),
+    Duration(MicroSeconds),
+}
+
+impl Period {
+    /// Number of cycles of a clock with the given frequency that make up the period
+    ///
+    /// A frequency of zero is an infinitely long period, and saturates.
+    pub(crate) fn cycles(self, clock: Hertz) -> u64 {
+        match self {
+            Period::Frequency(Hertz(0)) => u64::MAX,
+            Period::Frequency(f) => clock.0 as u64 / f.0 as u64,
+            Period::Duration(d) => clock.0 as u64 * d.0 as u64 / 1_000_000,
+        }
+    }
+}
+
+impl From<Hertz> for Period {
+    fn from(f: Hertz) -> Self {
+        Period::Frequency(f)
+    }
+}
+
+impl From<MicroSeconds> for Period {
+    fn from(d: MicroSeconds) -> Self {
+        Period::Duration(d)
+    }
+}
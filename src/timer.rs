@@ -5,10 +5,58 @@
 
 use core::marker::PhantomData;
 
+use crate::time_util::{Hertz, MicroSeconds, Period};
+
 pub trait TimerExt<Clk, Timer> {
     fn with_clock(self, clock: Clk) -> Timer;
 }
 
+/// Division of the HFPERCLK before it drives a timer
+///
+/// The discriminants are the values of the timer's PRESC field.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Prescaler {
+    Div1 = 0,
+    Div2 = 1,
+    Div4 = 2,
+    Div8 = 3,
+    Div16 = 4,
+    Div32 = 5,
+    Div64 = 6,
+    Div128 = 7,
+    Div256 = 8,
+    Div512 = 9,
+    Div1024 = 10,
+}
+
+/// Source of the events a timer counts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClockSource {
+    /// The HFPERCLK, divided by the timer's prescaler
+    Prescaled,
+    /// Edges on the channel 1 input, whose pin needs to be routed (eg. by `.capture()`)
+    CC1,
+    /// Overflows and underflows of the next lower numbered timer, for using two timers as a single
+    /// 32-bit counter
+    Cascade,
+}
+
+/// Direction in which a timer counts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CountMode {
+    /// Count up to TOP, then restart from 0
+    Up,
+    /// Count down to 0, then restart from TOP
+    Down,
+    /// Count up to TOP, then down to 0, and up again (eg. for center aligned PWM)
+    UpDown,
+    /// Count the edges of a quadrature encoder on the channel 0 and 1 inputs, counting on the
+    /// edges of channel 0 only
+    Quadrature2x,
+    /// Like Quadrature2x, but counting on the edges of both inputs
+    Quadrature4x,
+}
+
 /// Marker type for timer channels, signifying they're a CC channel 0 of whichever timer
 pub struct Channel0 {}
 /// Marker type for timer channels, signifying they're a CC channel 1 of whichever timer
@@ -41,7 +89,6 @@ impl<T, C> TimerChannel<T, C> {
 
 impl<T, C, P> RoutedTimerChannel<T, C, P> where
     P: crate::routing::HasLocForFunction<T, C>,
-    TimerChannel<T, C>: embedded_hal::PwmPin,
 {
     /// Free the routed timer channel's GPIO pin.
     ///
@@ -63,6 +110,167 @@ pub struct RoutedTimerChannel<Timer, Channel, Pin> {
     pub(crate) channel: TimerChannel<Timer, Channel>
 }
 
+/// Timer channels, routed or not, as they are combined in a TimerPwm
+pub trait AsTimerChannel<Timer, Channel> {
+    fn as_timer_channel(&mut self) -> &mut TimerChannel<Timer, Channel>;
+    fn as_timer_channel_ref(&self) -> &TimerChannel<Timer, Channel>;
+}
+
+impl<T, C> AsTimerChannel<T, C> for TimerChannel<T, C> {
+    fn as_timer_channel(&mut self) -> &mut TimerChannel<T, C> {
+        self
+    }
+    fn as_timer_channel_ref(&self) -> &TimerChannel<T, C> {
+        self
+    }
+}
+
+impl<T, C, P> AsTimerChannel<T, C> for RoutedTimerChannel<T, C, P> {
+    fn as_timer_channel(&mut self) -> &mut TimerChannel<T, C> {
+        &mut self.channel
+    }
+    fn as_timer_channel_ref(&self) -> &TimerChannel<T, C> {
+        &self.channel
+    }
+}
+
+/// Selection of a channel in the embedded_hal::Pwm implementation of TimerPwm
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Channel {
+    C0,
+    C1,
+    C2,
+}
+
+/// A timer whose channels are used together for PWM, obtained through a timer's `.pwm()` method.
+///
+/// The channels start out unrouted, and are routed to their pins with `.route0()` to `.route2()`
+/// (and unrouted with `.unroute0()` to `.unroute2()`). This implements embedded_hal::Pwm, where
+/// the period is set for all channels at once.
+///
+/// As with the PwmPin implementation of RoutedTimerChannel, duty cycle changes to enabled
+/// channels take effect at the start of the next period, so changes made to several channels
+/// within one period are applied together.
+pub struct TimerPwm<Timer, C0, C1, C2> {
+    timer: Timer,
+    channel0: C0,
+    channel1: C1,
+    channel2: C2,
+}
+
+impl<T, C1, C2> TimerPwm<T, TimerChannel<T, Channel0>, C1, C2> {
+    /// Route channel 0 to the given output pin.
+    pub fn route0<P>(self, pin: P) -> TimerPwm<T, RoutedTimerChannel<T, Channel0, P>, C1, C2> where
+        P: crate::routing::HasLocForFunction<T, Channel0>,
+    {
+        TimerPwm {
+            timer: self.timer,
+            channel0: self.channel0.route(pin),
+            channel1: self.channel1,
+            channel2: self.channel2,
+        }
+    }
+}
+
+impl<T, C0, C2> TimerPwm<T, C0, TimerChannel<T, Channel1>, C2> {
+    /// Route channel 1 to the given output pin.
+    pub fn route1<P>(self, pin: P) -> TimerPwm<T, C0, RoutedTimerChannel<T, Channel1, P>, C2> where
+        P: crate::routing::HasLocForFunction<T, Channel1>,
+    {
+        TimerPwm {
+            timer: self.timer,
+            channel0: self.channel0,
+            channel1: self.channel1.route(pin),
+            channel2: self.channel2,
+        }
+    }
+}
+
+impl<T, C0, C1> TimerPwm<T, C0, C1, TimerChannel<T, Channel2>> {
+    /// Route channel 2 to the given output pin.
+    pub fn route2<P>(self, pin: P) -> TimerPwm<T, C0, C1, RoutedTimerChannel<T, Channel2, P>> where
+        P: crate::routing::HasLocForFunction<T, Channel2>,
+    {
+        TimerPwm {
+            timer: self.timer,
+            channel0: self.channel0,
+            channel1: self.channel1,
+            channel2: self.channel2.route(pin),
+        }
+    }
+}
+
+impl<T, P, C1, C2> TimerPwm<T, RoutedTimerChannel<T, Channel0, P>, C1, C2> where
+    P: crate::routing::HasLocForFunction<T, Channel0>,
+{
+    /// Free channel 0's pin (see `RoutedTimerChannel::unroute()`).
+    pub fn unroute0(self) -> (TimerPwm<T, TimerChannel<T, Channel0>, C1, C2>, P) {
+        let (channel0, pin) = self.channel0.unroute();
+        (TimerPwm {
+            timer: self.timer,
+            channel0,
+            channel1: self.channel1,
+            channel2: self.channel2,
+        }, pin)
+    }
+}
+
+impl<T, P, C0, C2> TimerPwm<T, C0, RoutedTimerChannel<T, Channel1, P>, C2> where
+    P: crate::routing::HasLocForFunction<T, Channel1>,
+{
+    /// Free channel 1's pin (see `RoutedTimerChannel::unroute()`).
+    pub fn unroute1(self) -> (TimerPwm<T, C0, TimerChannel<T, Channel1>, C2>, P) {
+        let (channel1, pin) = self.channel1.unroute();
+        (TimerPwm {
+            timer: self.timer,
+            channel0: self.channel0,
+            channel1,
+            channel2: self.channel2,
+        }, pin)
+    }
+}
+
+impl<T, P, C0, C1> TimerPwm<T, C0, C1, RoutedTimerChannel<T, Channel2, P>> where
+    P: crate::routing::HasLocForFunction<T, Channel2>,
+{
+    /// Free channel 2's pin (see `RoutedTimerChannel::unroute()`).
+    pub fn unroute2(self) -> (TimerPwm<T, C0, C1, TimerChannel<T, Channel2>>, P) {
+        let (channel2, pin) = self.channel2.unroute();
+        (TimerPwm {
+            timer: self.timer,
+            channel0: self.channel0,
+            channel1: self.channel1,
+            channel2,
+        }, pin)
+    }
+}
+
+/// Signal edges on which a timer channel in input capture mode captures the counter value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CaptureEdge {
+    Rising,
+    Falling,
+    Both,
+}
+
+/// Error conditions when reading captured values
+#[derive(Debug)]
+pub enum CaptureError {
+    /// A value was captured while the timer's buffer was full, and got lost.
+    Overflow,
+}
+
+/// A timer channel that captures the counter value on edges of its routed input pin, obtained
+/// through `TimerChannel::capture()`.
+///
+/// To measure pulse widths, capture both edges and subtract consecutive values; to measure a
+/// frequency, capture rising edges only. Either way, the timer's TOP value (see `set_top()`) needs
+/// to be taken into account when the counter wrapped around between two values.
+pub struct CaptureTimerChannel<Timer, Channel, Pin> {
+    pin: Pin,
+    channel: TimerChannel<Timer, Channel>
+}
+
 macro_rules! timer {
     ($TIMERn: ident, $TIMERnClk: ident, $TimerN: ident, $timerN: ident, $channel: tt) => {
 
@@ -71,6 +279,7 @@ mod $timerN {
 use super::*;
 
 use crate::cmu;
+use crate::emu;
 use registers;
 
 impl TimerExt<cmu::$TIMERnClk, $TimerN> for registers::$TIMERn {
@@ -89,14 +298,93 @@ impl $TimerN {
     /// Configure the top value for this timer.
     ///
     /// As this limits the duty cycle, it can be read back using the PWM method get_max_duty().
+    /// When a PWM frequency is all that matters, `with_pwm_frequency()` picks it automatically.
     pub fn set_top(&mut self, top: u16) {
         self.register.top.modify(|_, w| unsafe { w.top().bits(top) });
     }
 
-    pub fn start(&mut self) {
+    /// Start the counter.
+    ///
+    /// While the counter runs, the timer keeps the device from entering energy modes below EM1.
+    ///
+    /// (This is not called `start` so that it does not hide `CountDown::start()`.)
+    pub fn run(&mut self) {
+        emu::block(emu::Blocker::$TimerN);
         self.register.cmd.write(|w| w.start().bit(true));
     }
 
+    /// Stop the counter, allowing the device to enter deeper energy modes again.
+    pub fn stop(&mut self) {
+        self.register.cmd.write(|w| w.stop().bit(true));
+        emu::unblock(emu::Blocker::$TimerN);
+    }
+
+    /// Configure the timer for PWM at the given frequency, choosing the prescaler and TOP value that
+    /// give the highest duty cycle resolution.
+    ///
+    /// This makes the timer count up from the `ClockSource::Prescaled` source; it still needs to be
+    /// started. Frequencies too low for the timer are clamped to the lowest possible one.
+    pub fn with_pwm_frequency(mut self, frequency: Hertz) -> Self {
+        self.set_period(frequency.into());
+        self
+    }
+
+    /// Set clock source, prescaler and TOP value for the timer to count up from the HFPERCLK and
+    /// overflow once per period, or as rarely as possible if the period is too long.
+    fn set_period(&mut self, period: Period) {
+        use crate::cmu::FrozenClock;
+
+        let (presc, top) = prescaler_and_top(period.cycles(self.clock.get_frequency()));
+
+        self.set_clock_source(ClockSource::Prescaled);
+        // Unsafe: Any prescaler value up to 10 is valid
+        self.register.ctrl.modify(|_, w| unsafe { w.presc().bits(presc) }.mode().up());
+        self.set_top(top);
+    }
+
+    /// Set the division of the HFPERCLK when running from the `ClockSource::Prescaled` source.
+    ///
+    /// The reset value is `Prescaler::Div1`.
+    pub fn set_prescaler(&mut self, prescaler: Prescaler) {
+        // Unsafe: The discriminants are valid PRESC values
+        self.register.ctrl.modify(|_, w| unsafe { w.presc().bits(prescaler as u8) });
+    }
+
+    /// Select what drives the counter. The reset value is `ClockSource::Prescaled`.
+    pub fn set_clock_source(&mut self, source: ClockSource) {
+        self.register.ctrl.modify(|_, w| match source {
+            ClockSource::Prescaled => w.clksel().preschfperclk(),
+            ClockSource::CC1 => w.clksel().cc1(),
+            ClockSource::Cascade => w.clksel().timerouf(),
+        });
+    }
+
+    /// Select the direction in which the counter runs. The reset value is `CountMode::Up`.
+    pub fn set_count_mode(&mut self, mode: CountMode) {
+        self.register.ctrl.modify(|_, w| match mode {
+            CountMode::Up => w.mode().up(),
+            CountMode::Down => w.mode().down(),
+            CountMode::UpDown => w.mode().updown(),
+            CountMode::Quadrature2x => w.mode().qdec().qdm().clear_bit(),
+            CountMode::Quadrature4x => w.mode().qdec().qdm().set_bit(),
+        });
+    }
+
+    /// Configure whether the timer keeps running while the CPU is halted by a debugger. By
+    /// default, it is frozen.
+    pub fn set_debug_run(&mut self, run: bool) {
+        self.register.ctrl.modify(|_, w| w.debugrun().bit(run));
+    }
+
+    /// Release the timer's register block and clock.
+    ///
+    /// The timer is stopped and its clock gate is disabled.
+    pub fn free(mut self) -> (registers::$TIMERn, cmu::$TIMERnClk) {
+        self.stop();
+        self.clock.disable();
+        (self.register, self.clock)
+    }
+
     #[cfg(not(feature = "_routing_per_function"))]
     /// Preconfigure a pin route on the whole device
     ///
@@ -110,8 +398,27 @@ impl $TimerN {
         self.register.route.modify(|_, w| w.location().variant(route))
     }
 
+    /// Use the timer's channels together for PWM.
+    ///
+    /// The timer is set to count up from the `ClockSource::Prescaled` source, keeping its
+    /// prescaler and TOP value. It can be taken back out with `TimerPwm::free()`.
+    pub fn pwm(mut self) -> TimerPwm<$TimerN, TimerChannel<$TimerN, Channel0>, TimerChannel<$TimerN, Channel1>, TimerChannel<$TimerN, Channel2>> {
+        self.set_clock_source(ClockSource::Prescaled);
+        self.set_count_mode(CountMode::Up);
+        TimerPwm {
+            timer: self,
+            channel0: TimerChannel { _phantom: PhantomData },
+            channel1: TimerChannel { _phantom: PhantomData },
+            channel2: TimerChannel { _phantom: PhantomData },
+        }
+    }
+
     /// Dissect this timer into its various channels, consuming the timer.
     ///
+    /// A timer that was started with `run()` keeps running (and keeps the device from entering
+    /// energy modes below EM1) for good, as nothing can stop it any more; a timer that was not
+    /// started does not block any energy modes.
+    ///
     /// The returning struct is non-public intentionally, as it is expected to grow when additional
     /// channels are implemented. Channels can be moved out of this struct as `.channel[0-2]`
     /// attributes.
@@ -132,12 +439,189 @@ pub struct Channels {
     pub channel2: TimerChannel<$TimerN, Channel2>,
 }
 
+/// A count down timer that is restarted whenever it expires, using the frequency of the timer's
+/// clock. The prescaler and TOP value are picked to match the requested period as closely as
+/// possible, and the timer is set to count up from the `ClockSource::Prescaled` clock source.
+///
+/// Periods longer than the timer can count (with a prescaler of 1024 and a TOP value of 0xffff,
+/// eg. about 1.4s at 48MHz) are clamped to that longest period.
+///
+/// ```ignore
+/// timer.start(Hertz(50));
+/// loop {
+///     block!(timer.wait()).unwrap();
+///     // runs every 20ms
+/// }
+/// ```
+impl embedded_hal::timer::CountDown for $TimerN {
+    type Time = Period;
+
+    fn start<T>(&mut self, count: T) where
+        T: Into<Period>,
+    {
+        self.stop();
+        self.set_period(count.into());
+        self.register.cnt.write(|w| unsafe { w.cnt().bits(0) });
+        self.register.ifc.write(|w| w.of().set_bit());
+        self.run();
+    }
+
+    fn wait(&mut self) -> nb::Result<(), void::Void> {
+        if self.register.if_.read().of().bit() {
+            self.register.ifc.write(|w| w.of().set_bit());
+            Ok(())
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+}
+
+impl embedded_hal::timer::Periodic for $TimerN {}
+
+impl<C0, C1, C2> TimerPwm<$TimerN, C0, C1, C2> where
+    C0: AsTimerChannel<$TimerN, Channel0>,
+    C1: AsTimerChannel<$TimerN, Channel1>,
+    C2: AsTimerChannel<$TimerN, Channel2>,
+{
+    /// Enable all channels at the same time.
+    ///
+    /// If the timer is running, it is stopped while the channels are enabled, which stretches the
+    /// current period by the few clock cycles that takes.
+    pub fn enable_all(&mut self) {
+        self.set_all_modes(ChannelMode::Pwm);
+    }
+
+    /// Disable all channels at the same time.
+    ///
+    /// If the timer is running, it is stopped while the channels are disabled (see
+    /// `enable_all()`).
+    pub fn disable_all(&mut self) {
+        self.set_all_modes(ChannelMode::Off);
+    }
+
+    fn set_all_modes(&mut self, mode: ChannelMode) {
+        let running = self.timer.register.status.read().running().bit();
+        self.timer.register.cmd.write(|w| w.stop().bit(true));
+        self.channel0.as_timer_channel().set_mode(mode);
+        self.channel1.as_timer_channel().set_mode(mode);
+        self.channel2.as_timer_channel().set_mode(mode);
+        if running {
+            self.timer.run();
+        }
+    }
+
+    /// Start the timer (see `run()` on the timer).
+    ///
+    /// Other than that, the timer is only configured through the Pwm trait, so that its clock
+    /// source and counting mode stay what `get_period()` expects.
+    pub fn run(&mut self) {
+        self.timer.run();
+    }
+
+    /// Stop the timer (see `stop()` on the timer).
+    pub fn stop(&mut self) {
+        self.timer.stop();
+    }
+
+    /// Configure whether the timer keeps running while the CPU is halted by a debugger (see
+    /// `set_debug_run()` on the timer).
+    pub fn set_debug_run(&mut self, run: bool) {
+        self.timer.set_debug_run(run);
+    }
+
+}
+
+impl TimerPwm<$TimerN, TimerChannel<$TimerN, Channel0>, TimerChannel<$TimerN, Channel1>, TimerChannel<$TimerN, Channel2>> {
+    /// Disable all channels, and give back the timer.
+    ///
+    /// This is only available once all channels are unrouted (see `unroute0()` etc.), as the
+    /// timer gives out its channels again.
+    pub fn free(mut self) -> $TimerN {
+        self.channel0.set_mode(ChannelMode::Off);
+        self.channel1.set_mode(ChannelMode::Off);
+        self.channel2.set_mode(ChannelMode::Off);
+        self.timer
+    }
+}
+
+impl<C0, C1, C2> embedded_hal::Pwm for TimerPwm<$TimerN, C0, C1, C2> where
+    C0: AsTimerChannel<$TimerN, Channel0>,
+    C1: AsTimerChannel<$TimerN, Channel1>,
+    C2: AsTimerChannel<$TimerN, Channel2>,
+{
+    type Channel = Channel;
+    type Time = Period;
+    type Duty = u16;
+
+    fn enable(&mut self, channel: Channel) {
+        match channel {
+            Channel::C0 => self.channel0.as_timer_channel().set_mode(ChannelMode::Pwm),
+            Channel::C1 => self.channel1.as_timer_channel().set_mode(ChannelMode::Pwm),
+            Channel::C2 => self.channel2.as_timer_channel().set_mode(ChannelMode::Pwm),
+        }
+    }
+
+    fn disable(&mut self, channel: Channel) {
+        match channel {
+            Channel::C0 => self.channel0.as_timer_channel().set_mode(ChannelMode::Off),
+            Channel::C1 => self.channel1.as_timer_channel().set_mode(ChannelMode::Off),
+            Channel::C2 => self.channel2.as_timer_channel().set_mode(ChannelMode::Off),
+        }
+    }
+
+    /// Get the PWM period.
+    ///
+    /// This is given as a frequency, unless it is below 1Hz, where it is given as a duration.
+    fn get_period(&self) -> Period {
+        use crate::cmu::FrozenClock;
+
+        // The timer counts up from the prescaled HFPERCLK, as set up by .pwm()
+        let ctrl = self.timer.register.ctrl.read();
+        let cycles = (self.timer.register.top.read().bits() + 1) << ctrl.presc().bits();
+        let clock = self.timer.clock.get_frequency().0;
+        if cycles <= clock {
+            Hertz(clock / cycles).into()
+        } else {
+            MicroSeconds((cycles as u64 * 1_000_000 / clock as u64) as u32).into()
+        }
+    }
+
+    fn get_duty(&self, channel: Channel) -> u16 {
+        match channel {
+            Channel::C0 => self.channel0.as_timer_channel_ref().get_compare_buffered(),
+            Channel::C1 => self.channel1.as_timer_channel_ref().get_compare_buffered(),
+            Channel::C2 => self.channel2.as_timer_channel_ref().get_compare_buffered(),
+        }
+    }
+
+    fn get_max_duty(&self) -> u16 {
+        self.timer.register.top.read().bits() as u16
+    }
+
+    fn set_duty(&mut self, channel: Channel, duty: u16) {
+        match channel {
+            Channel::C0 => self.channel0.as_timer_channel().set_duty(duty),
+            Channel::C1 => self.channel1.as_timer_channel().set_duty(duty),
+            Channel::C2 => self.channel2.as_timer_channel().set_duty(duty),
+        }
+    }
+
+    /// Set the PWM period, like `with_pwm_frequency()` does.
+    ///
+    /// As this changes the maximum duty, the duty cycles of all channels need to be set again.
+    fn set_period<P>(&mut self, period: P) where
+        P: Into<Period>,
+    {
+        self.timer.set_period(period.into());
+    }
+}
+
 // Needs to be actually repeated over the channels because the channel structs can't, for example,
 // produce a .cc0_ctrl.modify() artifact because there is nothing to be generic over.
 
-timerchannel!($TIMERn, $TimerN, $timerN, Channel0, cc0_ctrl, cc0_ccv, cc0_ccvb, cc0);
-timerchannel!($TIMERn, $TimerN, $timerN, Channel1, cc1_ctrl, cc1_ccv, cc1_ccvb, cc1);
-timerchannel!($TIMERn, $TimerN, $timerN, Channel2, cc2_ctrl, cc2_ccv, cc2_ccvb, cc2);
+timerchannel!($TIMERn, $TimerN, $timerN, Channel0, cc0_ctrl, cc0_ccv, cc0_ccvb, cc0, icbof0, icv0);
+timerchannel!($TIMERn, $TimerN, $timerN, Channel1, cc1_ctrl, cc1_ccv, cc1_ccvb, cc1, icbof1, icv1);
+timerchannel!($TIMERn, $TimerN, $timerN, Channel2, cc2_ctrl, cc2_ccv, cc2_ccvb, cc2, icbof2, icv2);
 
 }
 
@@ -147,7 +631,7 @@ pub use $timerN::$TimerN;
 }
 
 macro_rules! timerchannel {
-    ($TIMERn: ident, $TimerN: ident, $timerN: ident, $ChannelX: ident, $ccX_ctrl: ident, $ccX_ccv: ident, $ccX_ccvb: ident, $ccX: ident) => {
+    ($TIMERn: ident, $TimerN: ident, $timerN: ident, $ChannelX: ident, $ccX_ctrl: ident, $ccX_ccv: ident, $ccX_ccvb: ident, $ccX: ident, $icbofX: ident, $icvX: ident) => {
 
 impl TimerChannel<$TimerN, $ChannelX> {
     /// Get a pointer to the underlying timer's peripheral block.
@@ -194,6 +678,44 @@ impl TimerChannel<$TimerN, $ChannelX> {
         unsafe { &mut *Self::register() }.$ccX_ccvb.read().ccvb().bits()
     }
 
+    fn set_duty(&mut self, duty: u16) {
+        // FIXME find a way to set PWM glitch-free in either state without run-time overhead of
+        // type state
+        match self.get_mode() {
+            ChannelMode::Pwm => self.set_compare_buffered(duty),
+            _ => self.set_compare_unbuffered(duty),
+        }
+    }
+
+    /// Configure a route from the given input pin to the timer channel, and capture the counter
+    /// value whenever the selected edge occurs on it.
+    ///
+    /// With `filter` set, the input passes through a digital filter that suppresses pulses of only
+    /// a few timer clock cycles.
+    ///
+    /// The captured values can be read from the returned channel; the CCx interrupt (see
+    /// `interrupt_enable()`) fires whenever a value was captured.
+    pub fn capture<P>(mut self, pin: P, edge: CaptureEdge, filter: bool) -> CaptureTimerChannel<$TimerN, $ChannelX, P> where
+        P: crate::routing::HasInputLocForFunction<$TimerN, $ChannelX>,
+    {
+        // Unsafe: OK because it's a CCx register (see .register())
+        unsafe { &mut *Self::register() }.$ccX_ctrl.modify(|_, w| {
+            let w = w.filt().bit(filter);
+            match edge {
+                CaptureEdge::Rising => w.icedge().rising(),
+                CaptureEdge::Falling => w.icedge().falling(),
+                CaptureEdge::Both => w.icedge().both(),
+            }
+        });
+        unsafe { P::configure() };
+        self.set_mode(ChannelMode::InputCapture);
+
+        CaptureTimerChannel {
+            channel: self,
+            pin
+        }
+    }
+
     /// Consume the channel as a source of interrupts that trigger whenever a given counter is
     /// reached.
     ///
@@ -248,6 +770,66 @@ impl<P> RoutedTimerChannel<$TimerN, $ChannelX, P> {
         // Unsafe: OK because it's a CCx register (see .register())
         unsafe { &mut *self.register() }.$ccX_ctrl.modify(|_, w| w.outinv().bit(inverted));
     }
+
+    /// Set the duty cycle to the given fraction of the period.
+    ///
+    /// Unlike with `set_duty(get_max_duty())`, a fraction of 1 keeps the output constantly on
+    /// (unless TOP is at its maximum value of 0xffff, where it is off for one count). Fractions
+    /// above 1, including any with a zero denominator, are clamped to 1.
+    pub fn set_duty_fraction(&mut self, numerator: u32, denominator: u32) {
+        use embedded_hal::PwmPin;
+
+        // The output is on while the counter is below the compare value, so it takes TOP + 1 to
+        // keep it on for the full period
+        let period = self.get_max_duty() as u64 + 1;
+        let duty = if numerator >= denominator {
+            period
+        } else {
+            period * numerator as u64 / denominator as u64
+        };
+        self.set_duty(duty.min(0xffff) as u16);
+    }
+
+    /// Set the duty cycle to the given percentage of the period (see `set_duty_fraction`).
+    ///
+    /// Percentages above 100 are clamped to 100.
+    pub fn set_duty_percent(&mut self, percent: u8) {
+        self.set_duty_fraction(percent as u32, 100);
+    }
+}
+
+impl<P> CaptureTimerChannel<$TimerN, $ChannelX, P> where
+    P: crate::routing::HasInputLocForFunction<$TimerN, $ChannelX>,
+{
+    /// Read the oldest captured counter value.
+    ///
+    /// Up to two values are kept by the timer. If another edge occurs while both are unread, the
+    /// newest value is lost; this is reported once as a `CaptureError::Overflow`, after which the
+    /// values still buffered can be read.
+    pub fn read(&mut self) -> nb::Result<u16, CaptureError> {
+        // Unsafe: Reading CCV and STATUS has no side effects on other channels, and only this
+        // channel's overflow flag is cleared
+        let reg = unsafe { &*TimerChannel::<$TimerN, $ChannelX>::register() };
+
+        if reg.if_.read().$icbofX().bit() {
+            reg.ifc.write(|w| w.$icbofX().set_bit());
+            return Err(nb::Error::Other(CaptureError::Overflow));
+        }
+
+        if !reg.status.read().$icvX().bit() {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        Ok(reg.$ccX_ccv.read().ccv().bits())
+    }
+
+    /// Stop capturing, and free the channel and its input pin.
+    pub fn free(mut self) -> (TimerChannel<$TimerN, $ChannelX>, P) {
+        self.channel.set_mode(ChannelMode::Off);
+        unsafe { P::deconfigure() };
+
+        (self.channel, self.pin)
+    }
 }
 
 impl<P> embedded_hal::PwmPin for RoutedTimerChannel<$TimerN, $ChannelX, P> {
@@ -269,19 +851,29 @@ impl<P> embedded_hal::PwmPin for RoutedTimerChannel<$TimerN, $ChannelX, P> {
         unsafe { &*self.register() }.top.read().bits() as Self::Duty
     }
     fn set_duty(&mut self, duty: Self::Duty) {
-        // FIXME find a way to set PWM glitch-free in either state without run-time overhead of
-        // type state
-        match self.channel.get_mode() {
-            ChannelMode::Pwm => self.channel.set_compare_buffered(duty),
-            _ => self.channel.set_compare_unbuffered(duty),
-        }
+        self.channel.set_duty(duty)
+    }
+}
+
     }
 }
 
+/// Find the smallest prescaler (given as the exponent of its power of two) that allows counting
+/// the given number of timer clock cycles, and the TOP value that goes with it.
+///
+/// Cycle counts beyond what the timer can count saturate to the largest prescaler and TOP value.
+fn prescaler_and_top(cycles: u64) -> (u8, u16) {
+    // Number of prescaled cycles, rounded to nearest
+    let prescaled = |presc: u8| cycles.saturating_add((1 << presc) / 2) >> presc;
+    // The counter runs through TOP + 1 values
+    match (0..=10).find(|presc| prescaled(*presc) <= 1 << 16) {
+        Some(presc) => (presc, (prescaled(presc).max(1) - 1) as u16),
+        None => (10, 0xffff),
     }
 }
 
 /// Helper for TimerChannel.set_mode
+#[derive(Clone, Copy)]
 enum ChannelMode {
     Off,
     InputCapture,